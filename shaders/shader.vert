@@ -11,6 +11,25 @@ layout(set = 0, binding = 0) uniform WindowUniforms {
 	vec2 offset;
 	vec2 relative_size;
 	vec2 pixel_size;
+	vec2 transform_x;
+	vec2 transform_y;
+	uint show_raw_bayer;
+	uint flow_arrow_spacing;
+	vec4 invalid_depth_color;
+	vec4 checkerboard_colors[2];
+	uint diverging;
+	uint colormap;
+	float black_point;
+	float white_point;
+	float gamma;
+	uint contrast_mode;
+	float contrast_low;
+	float contrast_high;
+	uint channel;
+	uint checkerboard_size;
+	uint sample_filter;
+	uint show_pixel_grid;
+	uint show_pixel_values;
 };
 
 const vec2 POSITIONS[6] = vec2[6](
@@ -32,7 +51,9 @@ const vec2 TEXTURE_POSITIONS[6] = vec2[6](
 );
 
 void main() {
-	vec2 position = offset + relative_size * POSITIONS[gl_VertexIndex];
+	// Rotate and mirror the image within its bounding box.
+	vec2 corner = mat2(transform_x, transform_y) * (POSITIONS[gl_VertexIndex] - vec2(0.5, 0.5)) + vec2(0.5, 0.5);
+	vec2 position = offset + relative_size * corner;
 	position = 2.0 * position - vec2(1.0, 1.0);
 	gl_Position = vec4(position, 0.0, 1.0);
 	texture_coords = (pixel_size - vec2(1.0, 1.0)) * TEXTURE_POSITIONS[gl_VertexIndex];
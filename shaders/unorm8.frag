@@ -4,105 +4,985 @@
 layout(location = 0) in vec2 texture_coords;
 layout(location = 0) out vec4 out_color;
 
+layout(set = 0, binding = 0) uniform WindowUniforms {
+	vec2 offset;
+	vec2 relative_size;
+	vec2 pixel_size;
+	vec2 transform_x;
+	vec2 transform_y;
+	uint show_raw_bayer;
+	uint flow_arrow_spacing;
+	vec4 invalid_depth_color;
+	vec4 checkerboard_colors[2];
+	uint diverging;
+	uint colormap;
+	float black_point;
+	float white_point;
+	float gamma;
+	uint contrast_mode;
+	float contrast_low;
+	float contrast_high;
+	uint channel;
+	uint checkerboard_size;
+	uint sample_filter;
+	uint show_pixel_grid;
+	uint show_pixel_values;
+};
+
 layout(set = 1, binding = 0) uniform InfoBlock {
 	uint format;
 	uint width;
 	uint height;
 	uint stride_x;
 	uint stride_y;
+	uint stride_c;
+	uint sample_type;
+	float value_min;
+	float value_max;
+	uint chroma_offset_u;
+	uint chroma_offset_v;
+	uint chroma_stride_x;
+	uint chroma_stride_y;
+	uint yuv_matrix;
+	uint yuv_range;
+	uint bayer_pattern;
+	uint palette_offset;
+	uint palette_size;
+	uint flip_x;
+	uint flip_y;
+	uint contrast_lut_offset;
+	uint contrast_lut_size;
 };
 
 layout(set = 1, binding = 1) buffer Data {
 	uint data[];
 };
 
+const float PI = 3.14159265358979;
+
 uint extract_u8(uint i) {
 	uint word = data[i / 4];
 	uint offset = (i % 4) * 8;
 	return word >> offset & 0xFF;
 }
 
-float extract_unorm8(uint i) {
-	return float(extract_u8(i)) / 255.0;
+uint extract_u16(uint i) {
+	return extract_u8(i) | extract_u8(i + 1) << 8;
+}
+
+uint extract_u32(uint i) {
+	return extract_u16(i) | extract_u16(i + 2) << 16;
+}
+
+float extract_f16(uint i) {
+	uint bits = extract_u16(i);
+	uint sign = (bits & 0x8000) << 16;
+	uint exponent = (bits >> 10) & 0x1F;
+	uint mantissa = bits & 0x3FF;
+
+	// Subnormal numbers and zero.
+	if (exponent == 0) {
+		float value = float(mantissa) * exp2(-24.0);
+		return sign != 0 ? -value : value;
+
+	// Infinity and NaN.
+	} else if (exponent == 31) {
+		return uintBitsToFloat(sign | 0x7F800000 | mantissa << 13);
+
+	// Normal numbers.
+	} else {
+		return uintBitsToFloat(sign | (exponent + 112) << 23 | mantissa << 13);
+	}
+}
+
+float extract_f32(uint i) {
+	return uintBitsToFloat(extract_u32(i));
+}
+
+// Extract the raw value of sample `n` of the pixel starting at byte `i`.
+float extract_sample(uint i, uint n) {
+	i += n * stride_c;
+
+	// u8
+	if (sample_type == 0) {
+		return float(extract_u8(i));
+
+	// u16
+	} else if (sample_type == 1) {
+		return float(extract_u16(i));
+
+	// f16
+	} else if (sample_type == 2) {
+		return extract_f16(i);
+
+	// f32
+	} else if (sample_type == 3) {
+		return extract_f32(i);
+
+	// u32
+	} else if (sample_type == 4) {
+		return float(extract_u32(i));
+
+	// i8
+	} else if (sample_type == 5) {
+		return float(int(extract_u8(i) << 24) >> 24);
+
+	// i16
+	} else if (sample_type == 6) {
+		return float(int(extract_u16(i) << 16) >> 16);
+
+	// i32
+	} else {
+		return float(int(extract_u32(i)));
+	}
+}
+
+// Get the maximum raw value of the sample type.
+float sample_max() {
+	// u8
+	if (sample_type == 0) {
+		return 255.0;
+
+	// u16
+	} else if (sample_type == 1) {
+		return 65535.0;
+
+	// u32
+	} else if (sample_type == 4) {
+		return 4294967295.0;
+
+	// i8
+	} else if (sample_type == 5) {
+		return 127.0;
+
+	// i16
+	} else if (sample_type == 6) {
+		return 32767.0;
+
+	// i32
+	} else if (sample_type == 7) {
+		return 2147483647.0;
+
+	// f16, f32
+	} else {
+		return 1.0;
+	}
+}
+
+// Apply the contrast enhancement to a normalized sample value.
+float apply_contrast(float value) {
+	if (contrast_mode == 0) {
+		return value;
+	}
+
+	float t = clamp((value - contrast_low) / max(contrast_high - contrast_low, 1e-6), 0.0, 1.0);
+
+	// Histogram equalization, with linear interpolation between the entries of the lookup table.
+	// The lookup table is stored in the image buffer, and falls back to the linear stretch until it has been written.
+	if (contrast_mode == 2 && contrast_lut_size > 1) {
+		float position = t * float(contrast_lut_size - 1);
+		uint index = min(uint(position), contrast_lut_size - 2);
+		float low = extract_f32(contrast_lut_offset + index * 4);
+		float high = extract_f32(contrast_lut_offset + index * 4 + 4);
+		return mix(low, high, position - float(index));
+	}
+
+	return t;
+}
+
+// Extract sample `n` of the pixel starting at byte `i`, mapped from the value range to the range [0, 1].
+//
+// The contrast enhancement is applied to the result.
+float extract_unorm(uint i, uint n) {
+	return apply_contrast(clamp((extract_sample(i, n) - value_min) / (value_max - value_min), 0.0, 1.0));
+}
+
+// Extract sample `n` of the pixel starting at byte `i` as alpha value in the range [0, 1].
+float extract_alpha(uint i, uint n) {
+	return extract_sample(i, n) / sample_max();
+}
+
+// Convert 8-bit YUV values to RGB using the configured matrix and range.
+vec4 yuv_to_rgb(float y, float u, float v) {
+	// Full range
+	if (yuv_range == 0) {
+		y = y / 255.0;
+		u = (u - 128.0) / 255.0;
+		v = (v - 128.0) / 255.0;
+
+	// Limited range
+	} else {
+		y = (y - 16.0) / 219.0;
+		u = (u - 128.0) / 224.0;
+		v = (v - 128.0) / 224.0;
+	}
+
+	// BT.601
+	float kr = 0.299;
+	float kb = 0.114;
+
+	// BT.709
+	if (yuv_matrix == 1) {
+		kr = 0.2126;
+		kb = 0.0722;
+	}
+
+	float kg = 1.0 - kr - kb;
+	float r = y + 2.0 * (1.0 - kr) * v;
+	float g = y - 2.0 * kb * (1.0 - kb) / kg * u - 2.0 * kr * (1.0 - kr) / kg * v;
+	float b = y + 2.0 * (1.0 - kb) * u;
+	return vec4(clamp(vec3(r, g, b), 0.0, 1.0), 1.0);
+}
+
+// Get a YUV pixel with the luma sample at byte `i` and the chroma samples at position (cx, cy).
+vec4 get_yuv_pixel(uint i, uint cx, uint cy) {
+	uint c = cx * chroma_stride_x + cy * chroma_stride_y;
+	float y = float(extract_u8(i));
+	float u = float(extract_u8(chroma_offset_u + c));
+	float v = float(extract_u8(chroma_offset_v + c));
+	return yuv_to_rgb(y, u, v);
+}
+
+// Extract the mosaic value at (x, y) of a Bayer image.
+//
+// Coordinates outside the image are mirrored at the edges to preserve the color pattern.
+float extract_bayer(int x, int y) {
+	int w = int(width);
+	int h = int(height);
+	x = clamp(x < 0 ? -x : (x >= w ? 2 * w - 2 - x : x), 0, w - 1);
+	y = clamp(y < 0 ? -y : (y >= h ? 2 * h - 2 - y : y), 0, h - 1);
+	return extract_unorm(uint(x) * stride_x + uint(y) * stride_y, 0);
+}
+
+// Get the pixel at (x, y) of a Bayer image using bilinear demosaicing.
+vec4 get_bayer_pixel(uint x, uint y) {
+	int ix = int(x);
+	int iy = int(y);
+	float center = extract_bayer(ix, iy);
+	if (show_raw_bayer != 0) {
+		return vec4(center, center, center, 1.0);
+	}
+
+	float horizontal = (extract_bayer(ix - 1, iy) + extract_bayer(ix + 1, iy)) / 2.0;
+	float vertical = (extract_bayer(ix, iy - 1) + extract_bayer(ix, iy + 1)) / 2.0;
+	float adjacent = (horizontal + vertical) / 2.0;
+	float diagonal = (
+		extract_bayer(ix - 1, iy - 1) + extract_bayer(ix + 1, iy - 1)
+		+ extract_bayer(ix - 1, iy + 1) + extract_bayer(ix + 1, iy + 1)
+	) / 4.0;
+
+	// The location of the red filter in the top-left 2x2 block.
+	bool red_column = (x & 1) == (bayer_pattern & 1);
+	bool red_row = (y & 1) == (bayer_pattern >> 1);
+
+	if (red_row && red_column) {
+		return vec4(center, adjacent, diagonal, 1.0);
+	} else if (!red_row && !red_column) {
+		return vec4(diagonal, adjacent, center, 1.0);
+	} else if (red_row) {
+		return vec4(horizontal, center, vertical, 1.0);
+	} else {
+		return vec4(vertical, center, horizontal, 1.0);
+	}
+}
+
+// Extract the label at byte `i` as unsigned integer.
+uint extract_label(uint i) {
+	// u8
+	if (sample_type == 0) {
+		return extract_u8(i);
+
+	// u16
+	} else if (sample_type == 1) {
+		return extract_u16(i);
+
+	// u32
+	} else {
+		return extract_u32(i);
+	}
+}
+
+// Convert a color from HSV to RGB.
+vec3 hsv_to_rgb(float hue, float saturation, float value) {
+	vec3 k = clamp(abs(mod(hue * 6.0 + vec3(0.0, 4.0, 2.0), 6.0) - 3.0) - 1.0, 0.0, 1.0);
+	return value * mix(vec3(1.0), k, saturation);
+}
+
+// Get the color of a palette entry.
+vec4 palette_color(uint index) {
+	if (index >= palette_size) {
+		return vec4(0.0, 0.0, 0.0, 0.0);
+	}
+	uint i = palette_offset + index * 4;
+	return vec4(extract_u8(i), extract_u8(i + 1), extract_u8(i + 2), extract_u8(i + 3)) / 255.0;
+}
+
+// Get the color of a label from the user palette, or from the automatic palette.
+vec4 label_color(uint label) {
+	if (label < palette_size) {
+		return palette_color(label);
+	} else if (label == 0) {
+		return vec4(0.0, 0.0, 0.0, 0.0);
+	}
+
+	// Use a multiplicative hash to spread consecutive labels over the color wheel.
+	uint hash = label * 2654435761u;
+	float hue = float(hash >> 8) / 16777216.0;
+	float saturation = 1.0 - 0.15 * float(hash & 3);
+	float value = 1.0 - 0.15 * float((hash >> 2) & 3);
+	return vec4(hsv_to_rgb(hue, saturation, value), 1.0);
+}
+
+// Map a value in the range [0, 1] to a color using the Turbo colormap.
+//
+// This uses the polynomial approximation from https://gist.github.com/mikhailov-work/0d177465a8151eb6ede1768d51d476c7.
+vec3 turbo(float x) {
+	const vec4 red4 = vec4(0.13572138, 4.61539260, -42.66032258, 132.13108234);
+	const vec4 green4 = vec4(0.09140261, 2.19418839, 4.84296658, -14.18503333);
+	const vec4 blue4 = vec4(0.10667330, 12.64194608, -60.58204836, 110.36276771);
+	const vec2 red2 = vec2(-152.94239396, 59.28637943);
+	const vec2 green2 = vec2(4.27729857, 2.82956604);
+	const vec2 blue2 = vec2(-89.90310912, 27.34824973);
+
+	x = clamp(x, 0.0, 1.0);
+	vec4 v4 = vec4(1.0, x, x * x, x * x * x);
+	vec2 v2 = v4.zw * v4.z;
+	return vec3(
+		dot(v4, red4) + dot(v2, red2),
+		dot(v4, green4) + dot(v2, green2),
+		dot(v4, blue4) + dot(v2, blue2)
+	);
+}
+
+// Evaluate a polynomial colormap approximation with the given coefficients.
+vec3 polynomial_colormap(float x, vec3 c0, vec3 c1, vec3 c2, vec3 c3, vec3 c4, vec3 c5, vec3 c6) {
+	x = clamp(x, 0.0, 1.0);
+	return clamp(c0 + x * (c1 + x * (c2 + x * (c3 + x * (c4 + x * (c5 + x * c6))))), 0.0, 1.0);
+}
+
+// Map a value in the range [0, 1] to a color using the viridis colormap.
+//
+// This uses the polynomial approximation from https://www.shadertoy.com/view/WlfXRN.
+vec3 viridis(float x) {
+	return polynomial_colormap(x,
+		vec3(0.2777273272234177, 0.005407344544966578, 0.3340998053353061),
+		vec3(0.1050930431085774, 1.404613529898575, 1.384590162594685),
+		vec3(-0.3308618287255563, 0.214847559468213, 0.09509516302823659),
+		vec3(-4.634230498983486, -5.799100973351585, -19.33244095627987),
+		vec3(6.228269936347081, 14.17993336680509, 56.69055260068105),
+		vec3(4.776384997670288, -13.74514537774601, -65.35303263337234),
+		vec3(-5.435455855934631, 4.645852612178535, 26.3124352495832)
+	);
+}
+
+// Map a value in the range [0, 1] to a color using the magma colormap.
+//
+// This uses the polynomial approximation from https://www.shadertoy.com/view/WlfXRN.
+vec3 magma(float x) {
+	return polynomial_colormap(x,
+		vec3(-0.002136485053939582, -0.000749655052795221, -0.005386127855323933),
+		vec3(0.2516605407371642, 0.6775232436837668, 2.494026599312351),
+		vec3(8.353717279216625, -3.577719514958484, 0.3144679030132573),
+		vec3(-27.66873308576866, 14.26473078096533, -13.64921318813922),
+		vec3(52.17613981234068, -27.94360607168351, 12.94416944238394),
+		vec3(-50.76852536473588, 29.04658282127291, 4.23415299384598),
+		vec3(18.65570506591883, -11.48977351997711, -5.601961508734096)
+	);
+}
+
+// Map a value in the range [0, 1] to a color using the inferno colormap.
+//
+// This uses the polynomial approximation from https://www.shadertoy.com/view/WlfXRN.
+vec3 inferno(float x) {
+	return polynomial_colormap(x,
+		vec3(0.0002189403691192265, 0.001651004631001012, -0.01948089843709184),
+		vec3(0.1065134194856116, 0.5639564367884091, 3.932712388889277),
+		vec3(11.60249308247187, -3.972853965665698, -15.9423941062914),
+		vec3(-41.70399613139459, 17.43639888205313, 44.35414519872813),
+		vec3(77.162935699427, -33.40235894210092, -81.80730925738993),
+		vec3(-71.31942824499214, 32.62606426397723, 73.20951985803202),
+		vec3(25.13112622477341, -12.24266895238567, -23.07032500287172)
+	);
+}
+
+// Map a value in the range [0, 1] to a color using the jet colormap.
+vec3 jet(float x) {
+	x = clamp(x, 0.0, 1.0);
+	return clamp(vec3(1.5) - abs(4.0 * x - vec3(3.0, 2.0, 1.0)), 0.0, 1.0);
+}
+
+// Map a value in the range [0, 1] to a color using the selected colormap.
+vec3 apply_colormap(float x) {
+	if (colormap == 1) {
+		return viridis(x);
+	} else if (colormap == 2) {
+		return magma(x);
+	} else if (colormap == 3) {
+		return inferno(x);
+	} else if (colormap == 4) {
+		return turbo(x);
+	} else if (colormap == 5) {
+		return jet(x);
+	} else {
+		return vec3(x);
+	}
+}
+
+// Get the color of a depth sample at byte `i`.
+vec4 depth_color(uint i) {
+	float depth = extract_sample(i, 0);
+
+	// Invalid measurements.
+	if (depth == 0.0 || isnan(depth)) {
+		return invalid_depth_color;
+	}
+
+	// Use turbo by default, but allow the user to pick a different colormap.
+	if (colormap == 0) {
+		return vec4(turbo(extract_unorm(i, 0)), 1.0);
+	} else {
+		return vec4(apply_colormap(extract_unorm(i, 0)), 1.0);
+	}
+}
+
+// Get the color of an optical flow vector using the HSV color-wheel encoding.
+vec4 flow_color(vec2 flow) {
+	if (isnan(flow.x) || isnan(flow.y)) {
+		return vec4(0.0, 0.0, 0.0, 1.0);
+	}
+
+	float hue = fract(atan(flow.y, flow.x) / (2.0 * PI));
+	float saturation = clamp(length(flow) / value_max, 0.0, 1.0);
+	return vec4(hsv_to_rgb(hue, saturation, 1.0), 1.0);
+}
+
+// Map image coordinates to the location of the pixel in memory.
+uvec2 memory_position(uint x, uint y) {
+	if (flip_x != 0) {
+		x = width - 1 - x;
+	}
+	if (flip_y != 0) {
+		y = height - 1 - y;
+	}
+	return uvec2(x, y);
+}
+
+// Get the optical flow vector of the pixel at image coordinates (x, y).
+vec2 get_flow(uint x, uint y) {
+	uvec2 position = memory_position(x, y);
+	uint i = position.x * stride_x + position.y * stride_y;
+	return vec2(extract_sample(i, 0), extract_sample(i, 1));
+}
+
+// Get the distance from point `p` to the line segment from `a` to `b`.
+float segment_distance(vec2 p, vec2 a, vec2 b) {
+	vec2 ab = b - a;
+	float t = clamp(dot(p - a, ab) / max(dot(ab, ab), 1e-6), 0.0, 1.0);
+	return length(p - a - t * ab);
+}
+
+// Get the coverage of the flow arrow at the continuous image position `p`.
+//
+// Each grid cell shows a single arrow for the flow at the center of the cell.
+// The arrows are drawn about one window pixel wide, where `window_pixel` is the size of a window pixel in image pixels.
+float flow_arrow(vec2 p, float window_pixel) {
+	float spacing = float(flow_arrow_spacing);
+	vec2 cell = floor((p + 0.5) / spacing);
+	vec2 center = min(cell * spacing + 0.5 * spacing - 0.5, vec2(width - 1, height - 1));
+	vec2 flow = get_flow(uint(round(center.x)), uint(round(center.y)));
+	if (isnan(flow.x) || isnan(flow.y) || length(flow) == 0.0) {
+		return 0.0;
+	}
+
+	// Scale the arrow to fit inside the grid cell.
+	float len = min(length(flow) / value_max, 1.0) * 0.9 * spacing;
+	vec2 direction = normalize(flow);
+	vec2 normal = vec2(-direction.y, direction.x);
+	vec2 start = center - 0.5 * len * direction;
+	vec2 end = center + 0.5 * len * direction;
+	float head = 0.3 * len;
+
+	float dist = segment_distance(p, start, end);
+	dist = min(dist, segment_distance(p, end, end - head * (direction + 0.5 * normal)));
+	dist = min(dist, segment_distance(p, end, end - head * (direction - 0.5 * normal)));
+	return clamp(1.5 - dist / window_pixel, 0.0, 1.0);
+}
+
+// Get the display color of the monochrome sample at byte `i`.
+//
+// With the diverging colormap, zero is shown as white,
+// negative values as blue and positive values as red.
+// The bound of the value range that is furthest from zero gives the fully saturated color.
+vec3 mono_color(uint i) {
+	if (diverging != 0) {
+		float scale = max(abs(value_min), abs(value_max));
+		float t = clamp(extract_sample(i, 0) / scale, -1.0, 1.0);
+		if (t < 0.0) {
+			return mix(vec3(1.0), vec3(0.0, 0.25, 1.0), -t);
+		} else {
+			return mix(vec3(1.0), vec3(1.0, 0.0, 0.0), t);
+		}
+	}
+
+	return apply_colormap(extract_unorm(i, 0));
 }
 
 vec4 get_pixel(uint x, uint y) {
+	// Map the image coordinates to the location in memory.
+	uvec2 position = memory_position(x, y);
+	x = position.x;
+	y = position.y;
 	uint i = x * stride_x + y * stride_y;
 
-	// Mono8
+	// Mono
 	if (format == 0) {
-		float mono = extract_unorm8(i);
-		return vec4(mono, mono, mono, 1.0);
+		return vec4(mono_color(i), 1.0);
 
-	// MonoAlpha8(Unpremultiplied)
+	// MonoAlpha(Unpremultiplied)
 	} else if (format == 1) {
-		float mono = extract_unorm8(i);
-		float a    = extract_unorm8(i + 1);
-		return vec4(mono, mono, mono, a);
+		float mono = extract_unorm(i, 0);
+		float a    = extract_alpha(i, 1);
+		return vec4(apply_colormap(mono), a);
 
-	// MonoAlpha8(Premultiplied)
+	// MonoAlpha(Premultiplied)
 	} else if (format == 2) {
-		float a    = float(extract_u8(i + 1));
-		float mono = float(extract_u8(i)) / a;
-		return vec4(mono, mono, mono, a);
+		float a    = extract_alpha(i, 1);
+		float mono = extract_unorm(i, 0) / a;
+		return vec4(apply_colormap(mono), a);
 
-	// Bgr8
+	// Bgr
 	} else if (format == 3) {
-		float b = extract_unorm8(i + 0);
-		float g = extract_unorm8(i + 1);
-		float r = extract_unorm8(i + 2);
+		float b = extract_unorm(i, 0);
+		float g = extract_unorm(i, 1);
+		float r = extract_unorm(i, 2);
 		return vec4(r, g, b, 1.0);
 
-	// Bgra8(Unpremultiplied)
+	// Bgra(Unpremultiplied)
 	} else if (format == 4) {
-		float b = extract_unorm8(i + 0);
-		float g = extract_unorm8(i + 1);
-		float r = extract_unorm8(i + 2);
-		float a = extract_unorm8(i + 3);
+		float b = extract_unorm(i, 0);
+		float g = extract_unorm(i, 1);
+		float r = extract_unorm(i, 2);
+		float a = extract_alpha(i, 3);
 		return vec4(r, g, b, a);
 
-	// Bgra8(Premultiplied)
+	// Bgra(Premultiplied)
 	} else if (format == 5) {
-		float a = float(extract_u8(i + 3));
-		float b = float(extract_u8(i + 0)) / a;
-		float g = float(extract_u8(i + 1)) / a;
-		float r = float(extract_u8(i + 2)) / a;
-		return vec4(r, g, b, a / 255.0);
+		float a = extract_alpha(i, 3);
+		float b = extract_unorm(i, 0) / a;
+		float g = extract_unorm(i, 1) / a;
+		float r = extract_unorm(i, 2) / a;
+		return vec4(r, g, b, a);
 
-	// Rgb8
+	// Rgb
 	} else if (format == 6) {
-		float r = extract_unorm8(i + 0);
-		float g = extract_unorm8(i + 1);
-		float b = extract_unorm8(i + 2);
+		float r = extract_unorm(i, 0);
+		float g = extract_unorm(i, 1);
+		float b = extract_unorm(i, 2);
 		return vec4(r, g, b, 1.0);
 
-	// Rgba8(Unpremultiplied)
+	// Rgba(Unpremultiplied)
 	} else if (format == 7) {
-		float r = extract_unorm8(i + 0);
-		float g = extract_unorm8(i + 1);
-		float b = extract_unorm8(i + 2);
-		float a = extract_unorm8(i + 3);
+		float r = extract_unorm(i, 0);
+		float g = extract_unorm(i, 1);
+		float b = extract_unorm(i, 2);
+		float a = extract_alpha(i, 3);
 		return vec4(r, g, b, a);
 
-	// Rgba8(Premultiplied)
+	// Rgba(Premultiplied)
 	} else if (format == 8) {
-		float a = float(extract_u8(i + 3));
-		float r = float(extract_u8(i + 0)) / a;
-		float g = float(extract_u8(i + 1)) / a;
-		float b = float(extract_u8(i + 2)) / a;
-		return vec4(r, g, b, a / 255.0);
+		float a = extract_alpha(i, 3);
+		float r = extract_unorm(i, 0) / a;
+		float g = extract_unorm(i, 1) / a;
+		float b = extract_unorm(i, 2) / a;
+		return vec4(r, g, b, a);
+
+	// Yuv420
+	} else if (format == 9) {
+		return get_yuv_pixel(i, x / 2, y / 2);
+
+	// Yuyv
+	} else if (format == 10) {
+		return get_yuv_pixel(i, x / 2, y);
+
+	// Uyvy
+	} else if (format == 11) {
+		return get_yuv_pixel(i + 1, x / 2, y);
+
+	// Bayer
+	} else if (format == 12) {
+		return get_bayer_pixel(x, y);
+
+	// Label
+	} else if (format == 13) {
+		return label_color(extract_label(i));
+
+	// Indexed
+	} else if (format == 14) {
+		return palette_color(extract_u8(i));
+
+	// Depth
+	} else if (format == 15) {
+		return depth_color(i);
+
+	// Flow
+	} else if (format == 16) {
+		return flow_color(vec2(extract_sample(i, 0), extract_sample(i, 1)));
 
 	} else {
 		return vec4(1.0, 0.0, 1.0, 1.0);
 	}
 }
 
+// Apply the display range to a color.
+vec3 apply_display_range(vec3 color) {
+	color = clamp((color - black_point) / max(white_point - black_point, 1e-6), 0.0, 1.0);
+	return pow(color, vec3(1.0 / gamma));
+}
+
+// Draw a color with straight alpha over the checkerboard.
+vec4 draw_over_checkerboard(vec4 color) {
+	uvec2 square = uvec2(gl_FragCoord.xy) / checkerboard_size;
+	vec4 background = checkerboard_colors[(square.x + square.y) % 2];
+	float alpha = color.a + background.a * (1.0 - color.a);
+	if (alpha == 0.0) {
+		return vec4(0.0);
+	}
+	vec3 rgb = color.rgb * color.a + background.rgb * background.a * (1.0 - color.a);
+	return vec4(rgb / alpha, alpha);
+}
+
+// Get the pixel at the given coordinates clamped to the image, with premultiplied alpha.
+vec4 get_premultiplied_pixel(ivec2 position) {
+	uvec2 clamped = uvec2(clamp(position, ivec2(0, 0), ivec2(width - 1, height - 1)));
+	vec4 color = get_pixel(clamped.x, clamped.y);
+	return vec4(color.rgb * color.a, color.a);
+}
+
+// Convert a color with premultiplied alpha to straight alpha.
+vec4 unpremultiply(vec4 color) {
+	if (color.a == 0.0) {
+		return vec4(0.0, 0.0, 0.0, 0.0);
+	}
+	return vec4(color.rgb / color.a, color.a);
+}
+
+// Sample the image at a continuous position using bilinear interpolation.
+vec4 sample_bilinear(vec2 position) {
+	vec2 base = floor(position);
+	vec2 weight = position - base;
+	ivec2 i = ivec2(base);
+	vec4 top = mix(get_premultiplied_pixel(i), get_premultiplied_pixel(i + ivec2(1, 0)), weight.x);
+	vec4 bottom = mix(get_premultiplied_pixel(i + ivec2(0, 1)), get_premultiplied_pixel(i + ivec2(1, 1)), weight.x);
+	return unpremultiply(mix(top, bottom, weight.y));
+}
+
+// Sample the image at a continuous position using a box filter that covers `footprint` image pixels.
+//
+// To limit the cost, at most 8x8 image pixels are averaged, spread evenly over the footprint.
+vec4 sample_area(vec2 position, vec2 footprint) {
+	if (footprint.x <= 1.0 && footprint.y <= 1.0) {
+		return get_pixel(uint(round(position.x)), uint(round(position.y)));
+	}
+
+	uvec2 count = uvec2(clamp(ceil(footprint), vec2(1.0), vec2(8.0)));
+	vec2 spacing = footprint / vec2(count);
+	vec2 start = position - 0.5 * footprint + 0.5 * spacing;
+	vec4 sum = vec4(0.0);
+	for (uint y = 0; y < count.y; ++y) {
+		for (uint x = 0; x < count.x; ++x) {
+			sum += get_premultiplied_pixel(ivec2(round(start + spacing * vec2(x, y))));
+		}
+	}
+	return unpremultiply(sum / float(count.x * count.y));
+}
+
+// The minimum size of an image pixel in window pixels to draw the pixel grid.
+const float PIXEL_GRID_MIN_SIZE = 8.0;
+
+// The maximum number of characters of a formatted pixel value.
+const uint MAX_CHARS = 8;
+
+// Character codes for the pixel value font, after the digits 0 to 9.
+const uint CHAR_MINUS = 10;
+const uint CHAR_DOT = 11;
+const uint CHAR_E = 12;
+const uint CHAR_N = 13;
+const uint CHAR_A = 14;
+const uint CHAR_I = 15;
+const uint CHAR_F = 16;
+const uint CHAR_PLUS = 17;
+
+// A 3x5 pixel font for the pixel values.
+//
+// Each glyph is stored as 15 bits, with the top row in the most significant bits.
+const uint GLYPHS[18] = uint[18](
+	0x7B6F, 0x2C97, 0x73E7, 0x73CF, 0x5BC9, 0x79CF, 0x79EF, 0x7249, 0x7BEF, 0x7BCF,
+	0x01C0, 0x0002, 0x79E7, 0x0D6D, 0x076B, 0x2092, 0x35D2, 0x05D0
+);
+
+// Check if the glyph for a character has a dot at column `x` and row `y`.
+bool glyph_pixel(uint character, uint x, uint y) {
+	return (GLYPHS[character] >> (14u - (y * 3u + x)) & 1u) != 0u;
+}
+
+// Append a character to a text, if it still fits.
+void append_char(inout uint text[MAX_CHARS], inout uint len, uint character) {
+	if (len < MAX_CHARS) {
+		text[len] = character;
+		len += 1;
+	}
+}
+
+// Append the decimal digits of a number to a text, padded with zeros to at least `min_digits` digits.
+void append_uint(inout uint text[MAX_CHARS], inout uint len, uint value, uint min_digits) {
+	uint digits = 1;
+	uint power = 1;
+	while (value / power >= 10 || digits < min_digits) {
+		power *= 10;
+		digits += 1;
+	}
+	for (; power > 0; power /= 10) {
+		append_char(text, len, value / power % 10);
+	}
+}
+
+// Remove trailing zeros after the decimal dot, and the dot itself if nothing remains after it.
+void trim_fraction(inout uint text[MAX_CHARS], inout uint len, uint dot) {
+	while (len > dot + 1 && text[len - 1] == 0) {
+		len -= 1;
+	}
+	if (len == dot + 1) {
+		len -= 1;
+	}
+}
+
+// Format a value in scientific notation with up to three significant digits.
+void format_scientific(inout uint text[MAX_CHARS], inout uint len, float value) {
+	int exponent = int(floor(log(value) / log(10.0)));
+	uint mantissa = uint(round(value / pow(10.0, float(exponent)) * 100.0));
+	if (mantissa >= 1000) {
+		mantissa = uint(round(float(mantissa) / 10.0));
+		exponent += 1;
+	}
+
+	append_uint(text, len, mantissa / 100, 1);
+	uint dot = len;
+	append_char(text, len, CHAR_DOT);
+	append_uint(text, len, mantissa % 100, 2);
+	trim_fraction(text, len, dot);
+	append_char(text, len, CHAR_E);
+	if (exponent < 0) {
+		append_char(text, len, CHAR_MINUS);
+	}
+	append_uint(text, len, uint(abs(exponent)), 1);
+}
+
+// Format a value as text, returning the number of characters.
+//
+// Integers are shown exactly if they fit, other values are shown with about six significant digits.
+uint format_value(float value, bool integer, out uint text[MAX_CHARS]) {
+	uint len = 0;
+	if (isnan(value)) {
+		append_char(text, len, CHAR_N);
+		append_char(text, len, CHAR_A);
+		append_char(text, len, CHAR_N);
+		return len;
+	}
+
+	if (value < 0.0) {
+		append_char(text, len, CHAR_MINUS);
+	}
+	float magnitude = abs(value);
+
+	if (isinf(value)) {
+		append_char(text, len, CHAR_I);
+		append_char(text, len, CHAR_N);
+		append_char(text, len, CHAR_F);
+	} else if (integer && magnitude < 1e7) {
+		append_uint(text, len, uint(magnitude), 1);
+	} else if (magnitude == 0.0) {
+		append_char(text, len, 0);
+	} else if (magnitude >= 1e-3 && magnitude < 1e6) {
+		int exponent = int(floor(log(magnitude) / log(10.0)));
+		uint decimals = uint(clamp(5 - exponent, 0, 5));
+		uint scale = uint(pow(10.0, float(decimals)) + 0.5);
+		uint scaled = uint(round(magnitude * float(scale)));
+		append_uint(text, len, scaled / scale, 1);
+		if (decimals > 0) {
+			uint dot = len;
+			append_char(text, len, CHAR_DOT);
+			append_uint(text, len, scaled % scale, decimals);
+			trim_fraction(text, len, dot);
+		}
+	} else {
+		format_scientific(text, len, magnitude);
+	}
+	return len;
+}
+
+// Get the number of values shown for each pixel.
+uint pixel_value_count() {
+	if (format == 0 || format >= 12 && format <= 15) {
+		return 1;
+	} else if (format == 1 || format == 2 || format == 16) {
+		return 2;
+	} else if (format == 3 || format == 6 || format >= 9 && format <= 11) {
+		return 3;
+	} else {
+		return 4;
+	}
+}
+
+// Check if the values shown for each pixel are integers.
+bool pixel_values_are_integers() {
+	return format == 13 || format == 14 || format >= 9 && format <= 11 || sample_type != 2 && sample_type != 3;
+}
+
+// Get the raw value `n` of the pixel at image coordinates (x, y), in the order of the channels in memory.
+//
+// For YUV formats, the values are the Y, U and V samples.
+float pixel_value(uint x, uint y, uint n) {
+	uvec2 position = memory_position(x, y);
+	uint i = position.x * stride_x + position.y * stride_y;
+
+	// Yuv
+	if (format >= 9 && format <= 11) {
+		uvec2 chroma = format == 9 ? position / 2 : uvec2(position.x / 2, position.y);
+		uint c = chroma.x * chroma_stride_x + chroma.y * chroma_stride_y;
+		if (n == 0) {
+			return float(extract_u8(format == 11 ? i + 1 : i));
+		} else if (n == 1) {
+			return float(extract_u8(chroma_offset_u + c));
+		} else {
+			return float(extract_u8(chroma_offset_v + c));
+		}
+
+	// Label
+	} else if (format == 13) {
+		return float(extract_label(i));
+
+	// Indexed
+	} else if (format == 14) {
+		return float(extract_u8(i));
+
+	} else {
+		return extract_sample(i, n);
+	}
+}
+
+// Get the coverage of the pixel value text at position `local` in window pixels.
+//
+// The position is relative to the top left corner of a box of `cell_size` window pixels that is centered on the image pixel (x, y).
+// The box is aligned with the window, so the text stays upright when the image is rotated or mirrored.
+//
+// The text is scaled with the size of the image pixel, and is only drawn if it is large enough to be readable.
+float pixel_value_text(uint x, uint y, vec2 local, float cell_size) {
+	uint lines = pixel_value_count();
+
+	// Each character is 3x5 font pixels with one font pixel of spacing.
+	float scale = min(floor(cell_size / float(4 * MAX_CHARS + 2)), floor(cell_size / float(6 * lines + 2)));
+	scale = min(scale, 4.0);
+	if (scale < 2.0) {
+		return 0.0;
+	}
+
+	float block_height = float(6 * lines - 1) * scale;
+	float top = floor(0.5 * (cell_size - block_height));
+	int line = int(floor((local.y - top) / (6.0 * scale)));
+	if (line < 0 || line >= int(lines)) {
+		return 0.0;
+	}
+
+	uint text[MAX_CHARS];
+	uint len = format_value(pixel_value(x, y, uint(line)), pixel_values_are_integers(), text);
+	float line_width = float(4 * len - 1) * scale;
+	float left = floor(0.5 * (cell_size - line_width));
+	vec2 font = floor(vec2(local.x - left, local.y - top - float(line) * 6.0 * scale) / scale);
+	if (font.x < 0.0 || font.y < 0.0 || font.y >= 5.0) {
+		return 0.0;
+	}
+
+	uint column = uint(font.x) / 4;
+	uint glyph_x = uint(font.x) % 4;
+	if (column >= len || glyph_x >= 3) {
+		return 0.0;
+	}
+	return glyph_pixel(text[column], glyph_x, uint(font.y)) ? 1.0 : 0.0;
+}
+
+// Get the position of the current fragment in window pixels, relative to the center of the image pixel (x, y).
+//
+// The columns of `derivatives` are the change in texture coordinates per window pixel along the X and Y axis of the window.
+vec2 window_offset(uint x, uint y, mat2 derivatives) {
+	vec2 delta = texture_coords - vec2(x, y);
+	float determinant = derivatives[0][0] * derivatives[1][1] - derivatives[1][0] * derivatives[0][1];
+	return vec2(
+		derivatives[1][1] * delta.x - derivatives[1][0] * delta.y,
+		derivatives[0][0] * delta.y - derivatives[0][1] * delta.x
+	) / determinant;
+}
+
+// Draw the pixel grid and pixel values over a color.
+vec4 draw_pixel_annotations(vec4 color, uint x, uint y, float window_pixel, mat2 derivatives) {
+	float cell_size = 1.0 / window_pixel;
+	if (cell_size < PIXEL_GRID_MIN_SIZE) {
+		return color;
+	}
+
+	// Position in window pixels relative to the top left corner of the image pixel, along the axes of the image.
+	vec2 local = (texture_coords - vec2(x, y) + 0.5) * cell_size;
+
+	if (show_pixel_values != 0) {
+		float luminance = dot(color.rgb, vec3(0.2126, 0.7152, 0.0722));
+		vec4 text_color = luminance > 0.5 ? vec4(0.0, 0.0, 0.0, 1.0) : vec4(1.0, 1.0, 1.0, 1.0);
+		vec2 text_position = window_offset(x, y, derivatives) + 0.5 * cell_size;
+		color = mix(color, text_color, pixel_value_text(x, y, text_position, cell_size));
+	}
+
+	if (show_pixel_grid != 0) {
+		vec2 edge = min(local, vec2(cell_size) - local);
+		if (min(edge.x, edge.y) < 0.5) {
+			color = mix(color, vec4(0.5, 0.5, 0.5, 1.0), 0.75);
+		}
+	}
+
+	return color;
+}
+
 void main() {
+	// The derivatives must be computed outside of non-uniform control flow.
+	mat2 derivatives = mat2(dFdx(texture_coords), dFdy(texture_coords));
+
+	// The size of a window pixel in image pixels.
+	vec2 footprint = abs(derivatives[0]) + abs(derivatives[1]);
+	float window_pixel = max(footprint.x, footprint.y);
+
 	uint x = uint(round(texture_coords.x));
 	uint y = uint(round(texture_coords.y));
 	if (x >= width || y >= height) {
 		out_color = vec4(0.0, 0.0, 0.0, 0.0);
 	} else {
-		out_color = get_pixel(x, y);
+		if (sample_filter == 1) {
+			out_color = sample_bilinear(texture_coords);
+		} else if (sample_filter == 2) {
+			out_color = sample_area(texture_coords, footprint);
+		} else {
+			out_color = get_pixel(x, y);
+		}
+		if (channel != 0) {
+			float value = out_color[channel - 1];
+			out_color = vec4(value, value, value, 1.0);
+		}
+		out_color.rgb = apply_display_range(out_color.rgb);
+		if (checkerboard_size != 0) {
+			out_color = draw_over_checkerboard(out_color);
+		}
+		if (format == 16 && flow_arrow_spacing != 0) {
+			float arrow = flow_arrow(texture_coords, window_pixel);
+			out_color = mix(out_color, vec4(0.0, 0.0, 0.0, 1.0), arrow);
+		}
+		if (show_pixel_grid != 0 || show_pixel_values != 0) {
+			out_color = draw_pixel_annotations(out_color, x, y, window_pixel, derivatives);
+		}
 	}
 }
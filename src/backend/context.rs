@@ -13,13 +13,27 @@ use crate::error::NoSuitableAdapterFound;
 use crate::error::SetImageError;
 use crate::event::{self, Event, EventHandlerControlFlow, WindowEvent};
 use crate::AsImageView;
+use crate::Channel;
 use crate::ContextProxy;
 use crate::ImageInfo;
 use crate::Rectangle;
+use crate::ViewState;
 use crate::WindowHandle;
 use crate::WindowId;
 use crate::WindowOptions;
 
+/// The distance to pan with the keyboard, as fraction of the window size.
+const KEYBOARD_PAN_STEP: f32 = 0.1;
+
+/// The factor to zoom with the keyboard.
+const KEYBOARD_ZOOM_STEP: f32 = 1.25;
+
+/// The factor to zoom for each line scrolled with the mouse wheel.
+const SCROLL_ZOOM_STEP: f32 = 1.1;
+
+/// The number of pixels of a touchpad scroll that count as one line scrolled with a mouse wheel.
+const PIXELS_PER_SCROLL_LINE: f64 = 40.0;
+
 /// Internal shorthand type-alias for the correct [`winit::event_loop::EventLoop`].
 ///
 /// Not for use in public APIs.
@@ -254,6 +268,9 @@ impl<'a> ContextHandle<'a> {
 	}
 
 	/// Get the image info and the area where the image is drawn for a window.
+	///
+	/// The area is in physical pixels, with the origin at the top left of the window.
+	/// If the image is rotated, the area is the bounding box of the rotated image.
 	pub fn window_image_info(&self, window_id: WindowId) -> Result<Option<(ImageInfo, Rectangle)>, InvalidWindowId> {
 		let window = self.context.windows.iter().find(|x| x.id() == window_id).ok_or(InvalidWindowId { window_id })?;
 		let image_info = match window.image.as_ref().map(|x| *x.info()) {
@@ -261,19 +278,8 @@ impl<'a> ContextHandle<'a> {
 			None => return Ok(None),
 		};
 
-		let uniforms = window.calculate_uniforms();
 		let window_size = window.window.inner_size();
-
-		let [x, y] = uniforms.offset;
-		let [width, height] = uniforms.relative_size;
-
-		let x = (x * window_size.width as f32) as i32;
-		let y = (y * window_size.height as f32) as i32;
-		let width = (width * window_size.width as f32) as u32;
-		let height = (height * window_size.height as f32) as u32;
-
-		let image_area = Rectangle::from_xywh(x, y, width, height);
-
+		let image_area = window.calculate_uniforms().image_area([window_size.width, window_size.height]);
 		Ok(Some((image_info, image_area)))
 	}
 
@@ -282,6 +288,12 @@ impl<'a> ContextHandle<'a> {
 		self.context.set_window_visible(window_id, visible)
 	}
 
+	/// Get the options of a window.
+	pub fn window_options(&self, window_id: WindowId) -> Result<&WindowOptions, InvalidWindowId> {
+		let window = self.context.windows.iter().find(|x| x.id() == window_id).ok_or(InvalidWindowId { window_id })?;
+		Ok(&window.options)
+	}
+
 	/// Change the options of a window.
 	pub fn set_window_options<F>(&mut self, window_id: WindowId, make_options: F) -> Result<(), InvalidWindowId>
 	where
@@ -295,6 +307,10 @@ impl<'a> ContextHandle<'a> {
 			.ok_or(InvalidWindowId { window_id })?;
 		let options = (make_options)(&window.options);
 
+		if window.options.show_pixel_readout && !options.show_pixel_readout {
+			window.window.set_title(&window.title);
+		}
+
 		window.window.set_resizable(options.resizable);
 		window.window.set_decorations(!options.borderless);
 		if options.size != window.options.size {
@@ -304,10 +320,63 @@ impl<'a> ContextHandle<'a> {
 		}
 
 		window.options = options;
+		window.update_image_data(&self.context.device, &self.context.queue);
+		window.update_label_palette(&self.context.queue);
+		window.update_statistics(&self.context.queue);
+		window.uniforms.mark_dirty(true);
 		window.window.request_redraw();
 		Ok(())
 	}
 
+	/// Map a position in window coordinates to image coordinates for a window.
+	///
+	/// Returns [`None`] if the window has no image.
+	pub fn window_to_image(&self, window_id: WindowId, position: [f64; 2]) -> Result<Option<[f64; 2]>, InvalidWindowId> {
+		let window = self.context.windows.iter().find(|x| x.id() == window_id).ok_or(InvalidWindowId { window_id })?;
+		Ok(window.window_to_image(position))
+	}
+
+	/// Map a position in image coordinates to window coordinates for a window.
+	///
+	/// Returns [`None`] if the window has no image.
+	pub fn image_to_window(&self, window_id: WindowId, position: [f64; 2]) -> Result<Option<[f64; 2]>, InvalidWindowId> {
+		let window = self.context.windows.iter().find(|x| x.id() == window_id).ok_or(InvalidWindowId { window_id })?;
+		Ok(window.image_to_window(position))
+	}
+
+	/// Get the current zoom and pan of a window.
+	///
+	/// Returns [`None`] if the window has no image.
+	pub fn window_view(&self, window_id: WindowId) -> Result<Option<ViewState>, InvalidWindowId> {
+		let window = self.context.windows.iter().find(|x| x.id() == window_id).ok_or(InvalidWindowId { window_id })?;
+		Ok(window.view())
+	}
+
+	/// Set the zoom and pan of a window.
+	pub fn set_window_view(&mut self, window_id: WindowId, view: ViewState) -> Result<(), InvalidWindowId> {
+		self.context.update_window_view(window_id, |window| window.set_view(view))
+	}
+
+	/// Reset the zoom and pan of a window so that the image fits the window.
+	pub fn zoom_window_to_fit(&mut self, window_id: WindowId) -> Result<(), InvalidWindowId> {
+		self.context.update_window_view(window_id, |window| window.zoom_to_fit())
+	}
+
+	/// Zoom a window around its center so that one image pixel covers one window pixel.
+	pub fn zoom_window_to_actual_pixels(&mut self, window_id: WindowId) -> Result<(), InvalidWindowId> {
+		self.context.update_window_view(window_id, |window| window.zoom_to_actual_pixels())
+	}
+
+	/// Pan a window so that a position in image coordinates is shown at the center of the window.
+	pub fn center_window_on(&mut self, window_id: WindowId, position: [f64; 2]) -> Result<(), InvalidWindowId> {
+		self.context.update_window_view(window_id, |window| window.center_on(position))
+	}
+
+	/// Zoom and pan a window so that a rectangle of image pixels fills the window as much as possible.
+	pub fn zoom_window_to_rect(&mut self, window_id: WindowId, rect: Rectangle) -> Result<(), InvalidWindowId> {
+		self.context.update_window_view(window_id, |window| window.zoom_to_rect(rect))
+	}
+
 	/// Set the image to be displayed on a window.
 	pub fn set_window_image(
 		&mut self,
@@ -334,12 +403,10 @@ impl<'a> ContextHandle<'a> {
 			.iter_mut()
 			.find(|w| w.id() == window_id)
 			.ok_or(InvalidWindowId { window_id })?;
-		let image = GpuImage::from_data(
-			name.into(),
-			&self.context.device,
-			&self.context.image_bind_group_layout,
-			image.as_image_view()?,
-		);
+		let image = image.as_image_view()?;
+		image.validate()?;
+		let mut image = GpuImage::from_data(name.into(), &self.context.device, &self.context.image_bind_group_layout, image);
+		image.set_label_palette(&self.context.queue, window.options.label_palette.as_deref().unwrap_or(&[]));
 		window.overlays.push(image);
 		window.window.request_redraw();
 		Ok(())
@@ -407,8 +474,9 @@ impl Context {
 		title: impl Into<String>,
 		options: WindowOptions,
 	) -> Result<WindowId, CreateWindowError> {
+		let title = title.into();
 		let mut window = winit::window::WindowBuilder::new()
-			.with_title(title)
+			.with_title(title.clone())
 			.with_visible(!options.start_hidden)
 			.with_resizable(options.resizable)
 			.with_decorations(!options.borderless);
@@ -423,14 +491,19 @@ impl Context {
 		let surface = unsafe { self.instance.create_surface(&window) };
 		let swap_chain = create_swap_chain(window.inner_size(), &surface, self.swap_chain_format, &self.device);
 		let uniforms = UniformsBuffer::from_value(&self.device, &WindowUniforms::no_image(), &self.window_bind_group_layout);
+		let overlay_uniforms = UniformsBuffer::from_value(&self.device, &WindowUniforms::no_image(), &self.window_bind_group_layout);
 
 		let window = Window {
 			window,
+			title,
 			options,
 			surface,
 			swap_chain,
 			uniforms,
+			overlay_uniforms,
 			image: None,
+			image_data: None,
+			statistics: None,
 			zoom: 1.0,
 			translate: [0.0, 0.0],
 			overlays: Vec::new(),
@@ -472,8 +545,14 @@ impl Context {
 			.find(|w| w.id() == window_id)
 			.ok_or(InvalidWindowId { window_id })?;
 
-		let image = GpuImage::from_data(name, &self.device, &self.image_bind_group_layout, image.as_image_view()?);
+		let image = image.as_image_view()?;
+		image.validate()?;
+		window.image_data = if window.needs_image_data() { Some(Box::from(image.data())) } else { None };
+		let mut image = GpuImage::from_data(name, &self.device, &self.image_bind_group_layout, image);
+		image.set_label_palette(&self.queue, window.options.label_palette.as_deref().unwrap_or(&[]));
 		window.image = Some(image);
+		window.statistics = None;
+		window.update_statistics(&self.queue);
 		window.uniforms.mark_dirty(true);
 		window.window.request_redraw();
 		Ok(())
@@ -492,7 +571,9 @@ impl Context {
 		Ok(())
 	}
 
-	/// Zoom a window.
+	/// Zoom a window around the mouse cursor.
+	///
+	/// The delta is the number of scroll lines: every line zooms in or out by the same factor.
 	fn zoom_window(
 		&mut self,
 		window_id: WindowId,
@@ -508,7 +589,7 @@ impl Context {
 
 		let uniforms = window.calculate_uniforms();
 		let size = window.window.inner_size();
-		let zoom_factor = if delta > 0.0 { 1.1 } else { 1.0 / 1.1 };
+		let zoom_factor = SCROLL_ZOOM_STEP.powf(delta);
 		window.translate[0] += ((mouse_position_x / size.width as f32) - uniforms.offset[0]) * (1.0 - zoom_factor);
 		window.translate[1] += (1.0 - (mouse_position_y / size.height as f32) - uniforms.offset[1]) * (1.0 - zoom_factor);
 		window.zoom *= zoom_factor;
@@ -539,6 +620,90 @@ impl Context {
 		Ok(())
 	}
 
+	/// Change the zoom and pan of a window and redraw it.
+	fn update_window_view(&mut self, window_id: WindowId, update: impl FnOnce(&mut Window)) -> Result<(), InvalidWindowId> {
+		let window = self
+			.windows
+			.iter_mut()
+			.find(|w| w.id() == window_id)
+			.ok_or(InvalidWindowId { window_id })?;
+
+		update(window);
+		window.uniforms.mark_dirty(true);
+		window.window.request_redraw();
+		Ok(())
+	}
+
+	/// Adjust the display range of a window in response to a mouse drag.
+	///
+	/// Dragging horizontally over the full width of the window changes the width of the range by 1.
+	/// Dragging vertically over the full height of the window moves the center of the range by 1.
+	fn adjust_display_range(
+		&mut self,
+		window_id: WindowId,
+		delta_position_x: f32,
+		delta_position_y: f32,
+	) -> Result<(), InvalidWindowId> {
+		let window = self
+			.windows
+			.iter_mut()
+			.find(|w| w.id() == window_id)
+			.ok_or(InvalidWindowId { window_id })?;
+
+		let size = window.window.inner_size();
+		let range = &mut window.options.display_range;
+		let center = 0.5 * (range.black_point + range.white_point) + delta_position_y / size.height as f32;
+		let width = (range.white_point - range.black_point + delta_position_x / size.width as f32).max(1e-3);
+		range.black_point = center - 0.5 * width;
+		range.white_point = center + 0.5 * width;
+		window.uniforms.mark_dirty(true);
+		window.window.request_redraw();
+		Ok(())
+	}
+
+	/// Modify the options of a window in place and redraw it.
+	///
+	/// This is meant for options that only affect how the image is displayed.
+	fn update_window_options(&mut self, window_id: WindowId, update: impl FnOnce(&mut WindowOptions)) -> Result<(), InvalidWindowId> {
+		let window = self
+			.windows
+			.iter_mut()
+			.find(|w| w.id() == window_id)
+			.ok_or(InvalidWindowId { window_id })?;
+
+		update(&mut window.options);
+		window.update_image_data(&self.device, &self.queue);
+		window.update_statistics(&self.queue);
+		window.uniforms.mark_dirty(true);
+		window.window.request_redraw();
+		Ok(())
+	}
+
+	/// Update the pixel readout in the title of a window.
+	///
+	/// If `position` is `None` or outside of the image, the readout is removed from the title.
+	fn update_pixel_readout(
+		&mut self,
+		window_id: WindowId,
+		position: Option<winit::dpi::PhysicalPosition<f64>>,
+	) -> Result<(), InvalidWindowId> {
+		let window = self
+			.windows
+			.iter_mut()
+			.find(|w| w.id() == window_id)
+			.ok_or(InvalidWindowId { window_id })?;
+
+		if !window.options.show_pixel_readout {
+			return Ok(());
+		}
+
+		match position.and_then(|position| window.pixel_readout(position)) {
+			Some(readout) => window.window.set_title(&format!("{} - {}", window.title, readout)),
+			None => window.window.set_title(&window.title),
+		}
+		Ok(())
+	}
+
 	/// Render the contents of a window.
 	fn render_window(&mut self, window_id: WindowId) -> Result<(), InvalidWindowId> {
 		let window = self
@@ -560,9 +725,9 @@ impl Context {
 		let mut encoder = self.device.create_command_encoder(&Default::default());
 
 		if window.uniforms.is_dirty() {
-			window
-				.uniforms
-				.update_from(&self.device, &mut encoder, &window.calculate_uniforms());
+			let uniforms = window.calculate_uniforms();
+			window.uniforms.update_from(&self.device, &mut encoder, &uniforms);
+			window.overlay_uniforms.update_from(&self.device, &mut encoder, &uniforms.for_overlays());
 		}
 
 		render_pass(
@@ -578,7 +743,7 @@ impl Context {
 				render_pass(
 					&mut encoder,
 					&self.window_pipeline,
-					&window.uniforms,
+					&window.overlay_uniforms,
 					overlay,
 					None,
 					&frame.output.view,
@@ -612,8 +777,15 @@ impl Context {
 		let window_uniforms = WindowUniforms {
 			offset: [0.0, 0.0],
 			relative_size: [image.info().width as f32 / size.width as f32, 1.0],
-			pixel_size: [image.info().width as f32, image.info().height as f32],
+			..WindowUniforms::stretch([image.info().width as f32, image.info().height as f32])
 		};
+		let mut window_uniforms = window_uniforms
+			.set_display_options(&window.options)
+			.set_contrast(&window.options, window.statistics.as_ref());
+		if window.options.checkerboard.filter(|x| x.in_saved_images).is_none() {
+			window_uniforms.checkerboard_size = 0;
+		}
+		let overlay_uniforms = UniformsBuffer::from_value(&self.device, &window_uniforms.for_overlays(), &self.window_bind_group_layout);
 		let window_uniforms = UniformsBuffer::from_value(&self.device, &window_uniforms, &self.window_bind_group_layout);
 
 		let target = self.device.create_texture(&wgpu::TextureDescriptor {
@@ -649,7 +821,7 @@ impl Context {
 		);
 		if overlays {
 			for overlay in &window.overlays {
-				render_pass(&mut encoder, &self.image_pipeline, &window_uniforms, overlay, None, &render_target);
+				render_pass(&mut encoder, &self.image_pipeline, &overlay_uniforms, overlay, None, &render_target);
 			}
 		}
 
@@ -686,6 +858,11 @@ impl Context {
 			height: image.info().height,
 			stride_x: 4,
 			stride_y: bytes_per_row,
+			stride_c: 1,
+			value_range: crate::ValueRange::Full,
+			chroma: Default::default(),
+			flip_x: false,
+			flip_y: false,
 		};
 		let data: Box<[u8]> = Box::from(&view[..]);
 		Ok(Some((image.name().to_string(), crate::BoxImage::new(info, data))))
@@ -717,6 +894,11 @@ impl Context {
 			None => return,
 		};
 
+		// Add the position in image coordinates to mouse events.
+		if let Event::WindowEvent(event) = &mut event {
+			self.add_image_position(event);
+		}
+
 		// If we have nothing more to do, clean the background tasks.
 		if let Event::MainEventsCleared = &event {
 			self.clean_background_tasks();
@@ -735,17 +917,9 @@ impl Context {
 
 		// Perform default actions for events.
 		match event {
-			#[cfg(feature = "save")]
-			#[allow(deprecated)]
 			Event::WindowEvent(WindowEvent::KeyboardInput(event)) => {
-				if event.input.state.is_pressed() && event.input.key_code == Some(event::VirtualKeyCode::S) {
-					let overlays = event.input.modifiers.alt();
-					let modifiers = event.input.modifiers & !event::ModifiersState::ALT;
-					if modifiers == event::ModifiersState::CTRL {
-						self.save_image_prompt(event.window_id, overlays);
-					} else if modifiers == event::ModifiersState::CTRL | event::ModifiersState::SHIFT {
-						self.save_image(event.window_id, overlays);
-					}
+				if event.input.state.is_pressed() {
+					self.handle_key_press(&event);
 				}
 			},
 			Event::WindowEvent(WindowEvent::Resized(event)) => {
@@ -754,14 +928,37 @@ impl Context {
 				}
 			},
 			Event::WindowEvent(WindowEvent::MouseWheel(event)) => {
-				let delta = match event.delta {
-					winit::event::MouseScrollDelta::LineDelta(_, y) => y,
-					_ => 0.0
-				};
 				let current_position = self.mouse_cache.get_position(event.window_id, event.device_id).unwrap_or_else(|| [0.0, 0.0].into());
-				let _ = self.zoom_window(event.window_id, delta, current_position.x as f32, current_position.y as f32);
+				match event.delta {
+					winit::event::MouseScrollDelta::LineDelta(_, y) => {
+						let _ = self.zoom_window(event.window_id, y, current_position.x as f32, current_position.y as f32);
+					},
+					// Pixel deltas come from touchpads: scrolling pans the image, unless a zoom modifier is held.
+					winit::event::MouseScrollDelta::PixelDelta(delta) => {
+						if event.modifiers.ctrl() || event.modifiers.logo() {
+							let lines = (delta.y / PIXELS_PER_SCROLL_LINE) as f32;
+							let _ = self.zoom_window(event.window_id, lines, current_position.x as f32, current_position.y as f32);
+						} else {
+							let _ = self.pan_window(event.window_id, delta.x as f32, delta.y as f32);
+						}
+					},
+				}
+			},
+			Event::WindowEvent(WindowEvent::MouseLeave(event)) => {
+				let _ = self.update_pixel_readout(event.window_id, None);
 			},
 			Event::WindowEvent(WindowEvent::MouseMove(event)) => {
+				let _ = self.update_pixel_readout(event.window_id, Some(event.position));
+				if event.buttons.is_pressed(event::MouseButton::Right) {
+					let current_position = self.mouse_cache.get_position(event.window_id, event.device_id).unwrap_or_else(|| [0.0, 0.0].into());
+					let prev_position = self.mouse_cache.get_previous_position(event.window_id, event.device_id).unwrap_or_else(|| [0.0, 0.0].into());
+
+					let _ = self.adjust_display_range(
+						event.window_id,
+						(current_position.x - prev_position.x) as f32,
+						(current_position.y - prev_position.y) as f32,
+					);
+				}
 				if event.buttons.is_pressed(event::MouseButton::Left) {
 					let current_position = self.mouse_cache.get_position(event.window_id, event.device_id).unwrap_or_else(|| [0.0, 0.0].into());
 					let prev_position = self.mouse_cache.get_previous_position(event.window_id, event.device_id).unwrap_or_else(|| [0.0, 0.0].into());
@@ -783,6 +980,111 @@ impl Context {
 		}
 	}
 
+	/// Change the display options of a window in response to a key press.
+	///
+	/// Returns true if the key is a display shortcut.
+	fn handle_display_key(&mut self, window_id: WindowId, key_code: event::VirtualKeyCode, modifiers: event::ModifiersState) -> bool {
+		use event::VirtualKeyCode as Key;
+
+		if !modifiers.is_empty() {
+			return false;
+		}
+
+		let _ = match key_code {
+			Key::C => self.update_window_options(window_id, |options| options.colormap = options.colormap.next()),
+			Key::E => self.update_window_options(window_id, |options| options.contrast_mode = options.contrast_mode.next()),
+			Key::F => self.update_window_options(window_id, |options| options.filter = options.filter.next()),
+			Key::R => self.update_window_options(window_id, |options| toggle_channel(options, Channel::Red)),
+			Key::G => self.update_window_options(window_id, |options| toggle_channel(options, Channel::Green)),
+			Key::B => self.update_window_options(window_id, |options| toggle_channel(options, Channel::Blue)),
+			Key::A => self.update_window_options(window_id, |options| toggle_channel(options, Channel::Alpha)),
+			Key::LBracket => self.update_window_options(window_id, |options| options.view_transform = options.view_transform.rotate(-90.0)),
+			Key::RBracket => self.update_window_options(window_id, |options| options.view_transform = options.view_transform.rotate(90.0)),
+			Key::X => self.update_window_options(window_id, |options| options.view_transform.flip_horizontal ^= true),
+			Key::Y => self.update_window_options(window_id, |options| options.view_transform.flip_vertical ^= true),
+			_ => return false,
+		};
+		true
+	}
+
+	/// Zoom or pan a window in response to a key press.
+	///
+	/// Returns true if the key is a navigation key.
+	#[allow(deprecated)]
+	fn handle_navigation_key(&mut self, window_id: WindowId, key_code: event::VirtualKeyCode, modifiers: event::ModifiersState) -> bool {
+		use event::VirtualKeyCode as Key;
+
+		// The plus key is often typed with shift, so allow it for the zoom keys.
+		if !(modifiers - event::ModifiersState::SHIFT).is_empty() {
+			return false;
+		}
+		let shift = modifiers.shift();
+
+		let _ = match key_code {
+			Key::Left | Key::H if !shift => self.update_window_view(window_id, |window| window.translate[0] += KEYBOARD_PAN_STEP),
+			Key::Right | Key::L if !shift => self.update_window_view(window_id, |window| window.translate[0] -= KEYBOARD_PAN_STEP),
+			Key::Up | Key::K if !shift => self.update_window_view(window_id, |window| window.translate[1] -= KEYBOARD_PAN_STEP),
+			Key::Down | Key::J if !shift => self.update_window_view(window_id, |window| window.translate[1] += KEYBOARD_PAN_STEP),
+			Key::Plus | Key::Equals | Key::NumpadAdd => self.update_window_view(window_id, |window| window.zoom_by(KEYBOARD_ZOOM_STEP)),
+			Key::Minus | Key::NumpadSubtract => self.update_window_view(window_id, |window| window.zoom_by(1.0 / KEYBOARD_ZOOM_STEP)),
+			Key::Key0 | Key::Numpad0 if !shift => self.update_window_view(window_id, |window| window.zoom_to_fit()),
+			Key::Key1 | Key::Numpad1 if !shift => self.update_window_view(window_id, |window| window.zoom_to_actual_pixels()),
+			_ => return false,
+		};
+		true
+	}
+
+	/// Fill in the position in image coordinates for mouse events.
+	fn add_image_position(&self, event: &mut WindowEvent) {
+		let (window_id, position, image_position) = match event {
+			WindowEvent::MouseMove(event) => (event.window_id, Some(event.position), &mut event.image_position),
+			// The position of button events is a placeholder if the cursor position is not known yet.
+			WindowEvent::MouseButton(event) => {
+				let position = self.mouse_cache.get_position(event.window_id, event.device_id);
+				(event.window_id, position, &mut event.image_position)
+			},
+			WindowEvent::MouseWheel(event) => (event.window_id, event.position, &mut event.image_position),
+			_ => return,
+		};
+		if let Some(window) = self.windows.iter().find(|w| w.id() == window_id) {
+			*image_position = position.and_then(|position| window.window_to_image([position.x, position.y]));
+		}
+	}
+
+	/// Perform the default action for a key press in a window.
+	#[allow(deprecated)]
+	fn handle_key_press(&mut self, event: &event::WindowKeyboardInputEvent) {
+		let key_code = match event.input.key_code {
+			Some(x) => x,
+			None => return,
+		};
+
+		let (keyboard_navigation, display_shortcuts) = match self.windows.iter().find(|w| w.id() == event.window_id) {
+			Some(window) => (window.options.keyboard_navigation, window.options.display_shortcuts),
+			None => return,
+		};
+		if keyboard_navigation && self.handle_navigation_key(event.window_id, key_code, event.input.modifiers) {
+			return;
+		}
+		if display_shortcuts && self.handle_display_key(event.window_id, key_code, event.input.modifiers) {
+			return;
+		}
+
+		match key_code {
+			#[cfg(feature = "save")]
+			event::VirtualKeyCode::S => {
+				let overlays = event.input.modifiers.alt();
+				let modifiers = event.input.modifiers & !event::ModifiersState::ALT;
+				if modifiers == event::ModifiersState::CTRL {
+					self.save_image_prompt(event.window_id, overlays);
+				} else if modifiers == event::ModifiersState::CTRL | event::ModifiersState::SHIFT {
+					self.save_image(event.window_id, overlays);
+				}
+			},
+			_ => (),
+		}
+	}
+
 	/// Run global event handlers.
 	fn run_event_handlers(&mut self, event: &mut Event, event_loop: &EventLoopWindowTarget) {
 		use super::util::RetainMut;
@@ -990,7 +1292,7 @@ fn create_window_bind_group_layout(device: &wgpu::Device) -> wgpu::BindGroupLayo
 		label: Some("window_bind_group_layout"),
 		entries: &[wgpu::BindGroupLayoutEntry {
 			binding: 0,
-			visibility: wgpu::ShaderStage::VERTEX,
+			visibility: wgpu::ShaderStage::VERTEX | wgpu::ShaderStage::FRAGMENT,
 			count: None,
 			ty: wgpu::BindingType::Buffer {
 				ty: wgpu::BufferBindingType::Uniform,
@@ -1135,6 +1437,15 @@ fn render_pass(
 	drop(render_pass);
 }
 
+/// Show only the given channel, or show all channels again if the channel is already selected.
+fn toggle_channel(options: &mut WindowOptions, channel: Channel) {
+	if options.channel == Some(channel) {
+		options.channel = None;
+	} else {
+		options.channel = Some(channel);
+	}
+}
+
 fn align_next_u32(input: u32, alignment: u32) -> u32 {
 	let remainder = input % alignment;
 	if remainder == 0 {
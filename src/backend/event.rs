@@ -96,6 +96,7 @@ pub fn convert_winit_window_event(
 				window_id,
 				device_id,
 				position,
+				image_position: None,
 				modifiers,
 				buttons: mouse_cache.get_buttons(device_id).cloned().unwrap_or_default(),
 			}
@@ -123,6 +124,7 @@ pub fn convert_winit_window_event(
 				delta,
 				phase,
 				position: mouse_cache.get_position(window_id, device_id),
+				image_position: None,
 				previous_position: mouse_cache.get_previous_position(window_id, device_id),
 				buttons: mouse_cache.get_buttons(device_id).cloned().unwrap_or_default(),
 				modifiers,
@@ -141,6 +143,7 @@ pub fn convert_winit_window_event(
 				button: button.into(),
 				state: state.into(),
 				position: mouse_cache.get_position(window_id, device_id).unwrap_or_else(|| [-1.0, -1.0].into()),
+				image_position: None,
 				previous_position: mouse_cache.get_previous_position(window_id, device_id),
 				buttons: mouse_cache.get_buttons(device_id).cloned().unwrap_or_default(),
 				modifiers,
@@ -8,6 +8,14 @@ mod window;
 pub use context::ContextHandle;
 pub use proxy::ContextProxy;
 pub use proxy::WindowProxy;
+pub use window::Channel;
+pub use window::Checkerboard;
+pub use window::Colormap;
+pub use window::ContrastMode;
+pub use window::DisplayRange;
+pub use window::Filter;
+pub use window::ViewState;
+pub use window::ViewTransform;
 pub use window::WindowHandle;
 pub use window::WindowOptions;
 
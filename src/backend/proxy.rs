@@ -1,5 +1,9 @@
 use crate::ContextHandle;
+use crate::DisplayRange;
 use crate::Image;
+use crate::Rectangle;
+use crate::ViewState;
+use crate::ViewTransform;
 use crate::WindowHandle;
 use crate::WindowId;
 use crate::error::{InvalidWindowId, SetImageError};
@@ -252,6 +256,95 @@ impl WindowProxy {
 		self.run_function_wait(move |window| window.set_image(name, &image))
 	}
 
+	/// Get the display range of the window.
+	///
+	/// # Panics
+	/// This function will panic if called from within the context thread.
+	pub fn display_range(&self) -> Result<DisplayRange, InvalidWindowId> {
+		self.run_function_wait(move |window| window.display_range())
+	}
+
+	/// Set the display range of the window.
+	///
+	/// See [`DisplayRange`] for details.
+	///
+	/// # Panics
+	/// This function will panic if called from within the context thread.
+	pub fn set_display_range(&self, display_range: DisplayRange) -> Result<(), InvalidWindowId> {
+		self.run_function_wait(move |window| window.set_display_range(display_range))
+	}
+
+	/// Get the rotation and mirroring of the image in the window.
+	///
+	/// # Panics
+	/// This function will panic if called from within the context thread.
+	pub fn view_transform(&self) -> Result<ViewTransform, InvalidWindowId> {
+		self.run_function_wait(move |window| window.view_transform())
+	}
+
+	/// Set the rotation and mirroring of the image in the window.
+	///
+	/// See [`ViewTransform`] for details.
+	///
+	/// # Panics
+	/// This function will panic if called from within the context thread.
+	pub fn set_view_transform(&self, view_transform: ViewTransform) -> Result<(), InvalidWindowId> {
+		self.run_function_wait(move |window| window.set_view_transform(view_transform))
+	}
+
+	/// Get the current zoom and pan of the window.
+	///
+	/// Returns [`None`] if the window has no image.
+	///
+	/// # Panics
+	/// This function will panic if called from within the context thread.
+	pub fn view(&self) -> Result<Option<ViewState>, InvalidWindowId> {
+		self.run_function_wait(move |window| window.view())
+	}
+
+	/// Set the zoom and pan of the window.
+	///
+	/// See [`ViewState`] for details.
+	///
+	/// # Panics
+	/// This function will panic if called from within the context thread.
+	pub fn set_view(&self, view: ViewState) -> Result<(), InvalidWindowId> {
+		self.run_function_wait(move |window| window.set_view(view))
+	}
+
+	/// Reset the zoom and pan of the window so that the image fits the window.
+	///
+	/// # Panics
+	/// This function will panic if called from within the context thread.
+	pub fn zoom_to_fit(&self) -> Result<(), InvalidWindowId> {
+		self.run_function_wait(move |window| window.zoom_to_fit())
+	}
+
+	/// Zoom the window around its center so that one image pixel covers one window pixel.
+	///
+	/// # Panics
+	/// This function will panic if called from within the context thread.
+	pub fn zoom_to_actual_pixels(&self) -> Result<(), InvalidWindowId> {
+		self.run_function_wait(move |window| window.zoom_to_actual_pixels())
+	}
+
+	/// Pan the window so that a position in image coordinates is shown at the center of the window.
+	///
+	/// Image coordinates are in pixels, with the center of the top left pixel at (0, 0).
+	///
+	/// # Panics
+	/// This function will panic if called from within the context thread.
+	pub fn center_on(&self, position: [f64; 2]) -> Result<(), InvalidWindowId> {
+		self.run_function_wait(move |window| window.center_on(position))
+	}
+
+	/// Zoom and pan the window so that a rectangle of image pixels fills the window as much as possible.
+	///
+	/// # Panics
+	/// This function will panic if called from within the context thread.
+	pub fn zoom_to_rect(&self, rect: Rectangle) -> Result<(), InvalidWindowId> {
+		self.run_function_wait(move |window| window.zoom_to_rect(rect))
+	}
 
 	/// Add an event handler for the window.
 	///
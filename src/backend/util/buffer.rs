@@ -11,3 +11,10 @@ pub fn create_buffer_with_value<T>(device: &wgpu::Device, label: Option<&str>, v
 		device.create_buffer_init(&wgpu::util::BufferInitDescriptor { label, contents, usage })
 	}
 }
+
+/// Write an arbitrary object to a [`wgpu::Buffer`] at the given offset.
+pub fn write_buffer_with_value<T>(queue: &wgpu::Queue, buffer: &wgpu::Buffer, offset: wgpu::BufferAddress, value: &T) {
+	unsafe {
+		queue.write_buffer(buffer, offset, as_bytes(value));
+	}
+}
@@ -1,18 +1,30 @@
+use crate::Color;
 use crate::ImageInfo;
 use crate::ImageView;
-use crate::{Alpha, PixelFormat};
+use crate::image_info::SampleType;
+use crate::{Alpha, BayerPattern, PixelFormat, ValueRange, YuvMatrix, YuvRange};
 use super::buffer::create_buffer_with_value;
+use super::buffer::write_buffer_with_value;
+use super::statistics::supports_contrast;
+use super::statistics::EQUALIZATION_LUT_SIZE;
+
+/// The maximum number of colors in a palette.
+const MAX_PALETTE_SIZE: usize = 256;
 
 /// A GPU image buffer ready to be used with the rendering pipeline.
 pub struct GpuImage {
 	name: String,
 	info: ImageInfo,
+	data_size: usize,
+	value_range: (f32, f32),
 	bind_group: wgpu::BindGroup,
-	_uniforms: wgpu::Buffer,
-	_data: wgpu::Buffer,
+	uniforms: GpuImageUniforms,
+	uniforms_buffer: wgpu::Buffer,
+	data: wgpu::Buffer,
 }
 
 /// The uniforms associated with a [`GpuImage`].
+#[repr(C)]
 #[derive(Debug, Copy, Clone)]
 pub struct GpuImageUniforms {
 	format: u32,
@@ -20,6 +32,23 @@ pub struct GpuImageUniforms {
 	height: u32,
 	stride_x: u32,
 	stride_y: u32,
+	stride_c: u32,
+	sample_type: u32,
+	value_min: f32,
+	value_max: f32,
+	chroma_offset_u: u32,
+	chroma_offset_v: u32,
+	chroma_stride_x: u32,
+	chroma_stride_y: u32,
+	yuv_matrix: u32,
+	yuv_range: u32,
+	bayer_pattern: u32,
+	palette_offset: u32,
+	palette_size: u32,
+	flip_x: u32,
+	flip_y: u32,
+	contrast_lut_offset: u32,
+	contrast_lut_size: u32,
 }
 
 impl GpuImage {
@@ -37,29 +66,145 @@ impl GpuImage {
 			PixelFormat::Rgb8 => 6,
 			PixelFormat::Rgba8(Alpha::Unpremultiplied) => 7,
 			PixelFormat::Rgba8(Alpha::Premultiplied) => 8,
+			PixelFormat::Mono16 => 0,
+			PixelFormat::MonoAlpha16(Alpha::Unpremultiplied) => 1,
+			PixelFormat::MonoAlpha16(Alpha::Premultiplied) => 2,
+			PixelFormat::Rgb16 => 6,
+			PixelFormat::Rgba16(Alpha::Unpremultiplied) => 7,
+			PixelFormat::Rgba16(Alpha::Premultiplied) => 8,
+			PixelFormat::Mono16F => 0,
+			PixelFormat::Rgb16F => 6,
+			PixelFormat::Rgba16F(Alpha::Unpremultiplied) => 7,
+			PixelFormat::Rgba16F(Alpha::Premultiplied) => 8,
+			PixelFormat::Mono32F => 0,
+			PixelFormat::Rgb32F => 6,
+			PixelFormat::Rgba32F(Alpha::Unpremultiplied) => 7,
+			PixelFormat::Rgba32F(Alpha::Premultiplied) => 8,
+			PixelFormat::Mono8I => 0,
+			PixelFormat::Mono16I => 0,
+			PixelFormat::Mono32I => 0,
+			PixelFormat::Nv12(_) => 9,
+			PixelFormat::Nv21(_) => 9,
+			PixelFormat::I420(_) => 9,
+			PixelFormat::Yuyv(_) => 10,
+			PixelFormat::Uyvy(_) => 11,
+			PixelFormat::Bayer8(_) => 12,
+			PixelFormat::Bayer16(_) => 12,
+			PixelFormat::Label8 => 13,
+			PixelFormat::Label16 => 13,
+			PixelFormat::Label32 => 13,
+			PixelFormat::Indexed8 => 14,
+			PixelFormat::Depth16 => 15,
+			PixelFormat::Depth32F => 15,
+			PixelFormat::Flow32F => 16,
+		};
+
+		// The shader uses the location of the red filter in the top-left 2x2 block.
+		let bayer_pattern = match info.pixel_format.bayer_pattern() {
+			None => 0,
+			Some(BayerPattern::Rggb) => 0,
+			Some(BayerPattern::Grbg) => 1,
+			Some(BayerPattern::Gbrg) => 2,
+			Some(BayerPattern::Bggr) => 3,
+		};
+
+		let (yuv_matrix, yuv_range) = match info.pixel_format.yuv_encoding() {
+			None => (0, 0),
+			Some(encoding) => {
+				let matrix = match encoding.matrix {
+					YuvMatrix::Bt601 => 0,
+					YuvMatrix::Bt709 => 1,
+				};
+				let range = match encoding.range {
+					YuvRange::Full => 0,
+					YuvRange::Limited => 1,
+				};
+				(matrix, range)
+			},
 		};
 
+		let (sample_type, sample_min, sample_max) = match info.pixel_format.sample_type() {
+			SampleType::U8 => (0, 0.0, f32::from(u8::MAX)),
+			SampleType::U16 => (1, 0.0, f32::from(u16::MAX)),
+			SampleType::F16 => (2, 0.0, 1.0),
+			SampleType::F32 => (3, 0.0, 1.0),
+			SampleType::U32 => (4, 0.0, u32::MAX as f32),
+			SampleType::I8 => (5, f32::from(i8::MIN), f32::from(i8::MAX)),
+			SampleType::I16 => (6, f32::from(i16::MIN), f32::from(i16::MAX)),
+			SampleType::I32 => (7, i32::MIN as f32, i32::MAX as f32),
+		};
+
+		let (value_min, value_max) = value_range(&image, sample_min, sample_max);
+
+		// Only upload the bytes spanned by the image, not any trailing data of the buffer.
+		let image_data = image.data();
+		let image_data = match info.required_size() {
+			Ok(size) => &image_data[..image_data.len().min(size as usize)],
+			Err(_) => image_data,
+		};
+
+		// Palettes are stored in the same buffer as the image data, starting at the next word boundary.
+		// Indexed images carry their own palette.
+		// Label images reserve space for a user supplied palette, which is written by `set_label_palette()`.
+		let palette = image.palette().unwrap_or(&[]);
+		let palette = &palette[..palette.len().min(MAX_PALETTE_SIZE)];
+		let palette_capacity = if info.pixel_format.is_label() { MAX_PALETTE_SIZE } else { palette.len() };
+		let palette_offset = image_data.len() + (4 - image_data.len() % 4) % 4;
+
+		// The lookup table for histogram equalization follows the palette.
+		// It is written by `set_contrast_lut()` once the image statistics are known.
+		let contrast_lut_capacity = if supports_contrast(info.pixel_format) { EQUALIZATION_LUT_SIZE } else { 0 };
+		let contrast_lut_offset = palette_offset + palette_capacity * 4;
+		let buffer_size = (contrast_lut_offset + contrast_lut_capacity * 4).max(wgpu::COPY_BUFFER_ALIGNMENT as usize);
+
 		let uniforms = GpuImageUniforms {
 			format,
 			width: info.width,
 			height: info.height,
 			stride_x: info.stride_x,
 			stride_y: info.stride_y,
+			stride_c: info.stride_c,
+			sample_type,
+			value_min,
+			value_max,
+			chroma_offset_u: info.chroma.offset_u,
+			chroma_offset_v: info.chroma.offset_v,
+			chroma_stride_x: info.chroma.stride_x,
+			chroma_stride_y: info.chroma.stride_y,
+			yuv_matrix,
+			yuv_range,
+			bayer_pattern,
+			palette_offset: palette_offset as u32,
+			palette_size: palette.len() as u32,
+			flip_x: u32::from(info.flip_x),
+			flip_y: u32::from(info.flip_y),
+			contrast_lut_offset: contrast_lut_offset as u32,
+			contrast_lut_size: 0,
 		};
 
-		let uniforms = create_buffer_with_value(
+		let uniforms_buffer = create_buffer_with_value(
 			device,
 			Some(&format!("{}_uniforms_buffer", name)),
 			&uniforms,
-			wgpu::BufferUsage::UNIFORM,
+			wgpu::BufferUsage::UNIFORM | wgpu::BufferUsage::COPY_DST,
 		);
 
-		use wgpu::util::DeviceExt;
-		let data = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+		// Write the image data and palette directly into the mapped buffer, to avoid copying the image on the CPU first.
+		// The rest of the buffer is zero initialized by wgpu.
+		let data = device.create_buffer(&wgpu::BufferDescriptor {
 			label: Some(&format!("{}_image_buffer", name)),
-			contents: image.data(),
-			usage: wgpu::BufferUsage::STORAGE,
+			size: buffer_size as u64,
+			usage: wgpu::BufferUsage::STORAGE | wgpu::BufferUsage::COPY_SRC | wgpu::BufferUsage::COPY_DST,
+			mapped_at_creation: true,
 		});
+		{
+			let mut contents = data.slice(..).get_mapped_range_mut();
+			contents[..image_data.len()].copy_from_slice(image_data);
+			for (i, color) in palette.iter().enumerate() {
+				contents[palette_offset + i * 4..][..4].copy_from_slice(color);
+			}
+		}
+		data.unmap();
 
 		let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
 			label: Some(&format!("{}_bind_group", name)),
@@ -68,7 +213,7 @@ impl GpuImage {
 				wgpu::BindGroupEntry {
 					binding: 0,
 					resource: wgpu::BindingResource::Buffer(wgpu::BufferBinding {
-						buffer: &uniforms,
+						buffer: &uniforms_buffer,
 						offset: 0,
 						size: None, // Use entire buffer.
 					}),
@@ -87,9 +232,12 @@ impl GpuImage {
 		Self {
 			name,
 			info,
+			data_size: image_data.len(),
+			value_range: (value_min, value_max),
 			bind_group,
-			_uniforms: uniforms,
-			_data: data,
+			uniforms,
+			uniforms_buffer,
+			data,
 		}
 	}
 
@@ -103,8 +251,272 @@ impl GpuImage {
 		&self.info
 	}
 
+	/// Get the sample values that are mapped to black and full intensity.
+	///
+	/// This is the resolved value range of the image info, so it is also available for [`ValueRange::Auto`].
+	pub fn value_range(&self) -> (f32, f32) {
+		self.value_range
+	}
+
 	/// Get the bind group that should be used to render the image with the rendering pipeline.
 	pub fn bind_group(&self) -> &wgpu::BindGroup {
 		&self.bind_group
 	}
+
+	/// Set the user supplied colors for a label image.
+	///
+	/// Only the first 256 colors are used.
+	/// This does nothing if the image is not a label image.
+	pub fn set_label_palette(&mut self, queue: &wgpu::Queue, palette: &[Color]) {
+		if !self.info.pixel_format.is_label() {
+			return;
+		}
+
+		let palette = &palette[..palette.len().min(MAX_PALETTE_SIZE)];
+		if !palette.is_empty() {
+			let contents: Vec<u8> = palette.iter().flat_map(color_to_rgba8).collect();
+			queue.write_buffer(&self.data, self.uniforms.palette_offset.into(), &contents);
+		}
+		self.uniforms.palette_size = palette.len() as u32;
+		write_buffer_with_value(queue, &self.uniforms_buffer, 0, &self.uniforms);
+	}
+
+	/// Set the lookup table used for histogram equalization.
+	///
+	/// This does nothing if the pixel format of the image does not support contrast enhancement.
+	pub fn set_contrast_lut(&mut self, queue: &wgpu::Queue, lut: &[f32; EQUALIZATION_LUT_SIZE]) {
+		if !supports_contrast(self.info.pixel_format) {
+			return;
+		}
+
+		let contents: Vec<u8> = lut.iter().flat_map(|x| x.to_ne_bytes()).collect();
+		queue.write_buffer(&self.data, self.uniforms.contrast_lut_offset.into(), &contents);
+		self.uniforms.contrast_lut_size = EQUALIZATION_LUT_SIZE as u32;
+		write_buffer_with_value(queue, &self.uniforms_buffer, 0, &self.uniforms);
+	}
+
+	/// Read the image data back from the GPU.
+	///
+	/// This blocks until the data has been copied from the GPU buffer.
+	pub fn read_data(&self, device: &wgpu::Device, queue: &wgpu::Queue) -> Result<Box<[u8]>, wgpu::BufferAsyncError> {
+		// Buffer copies must be a multiple of 4 bytes, but the GPU buffer is padded to that size anyway.
+		let align_mask = wgpu::COPY_BUFFER_ALIGNMENT - 1;
+		let size = ((self.data_size as u64 + align_mask) & !align_mask).max(wgpu::COPY_BUFFER_ALIGNMENT);
+
+		let buffer = device.create_buffer(&wgpu::BufferDescriptor {
+			label: Some(&format!("{}_image_read_buffer", self.name)),
+			size,
+			usage: wgpu::BufferUsage::COPY_DST | wgpu::BufferUsage::MAP_READ,
+			mapped_at_creation: false,
+		});
+
+		let mut encoder = device.create_command_encoder(&Default::default());
+		encoder.copy_buffer_to_buffer(&self.data, 0, &buffer, 0, size);
+		queue.submit(std::iter::once(encoder.finish()));
+
+		let view = super::map_buffer(device, buffer.slice(..))?;
+		Ok(Box::from(&view[..self.data_size]))
+	}
+}
+
+/// Convert a color to 8-bit RGBA components, as used by the palettes in the image buffer.
+fn color_to_rgba8(color: &Color) -> [u8; 4] {
+	let convert = |x: f64| (x.clamp(0.0, 1.0) * 255.0).round() as u8;
+	[convert(color.red), convert(color.green), convert(color.blue), convert(color.alpha)]
+}
+
+/// Get the range of sample values that is mapped to the full display range.
+///
+/// A range with the same minimum and maximum is widened to avoid a division by zero in the shader.
+fn value_range(image: &ImageView, sample_min: f32, sample_max: f32) -> (f32, f32) {
+	let (min, max) = match image.info().value_range {
+		ValueRange::Full => (sample_min, sample_max),
+		ValueRange::Bits(bits) if sample_min < 0.0 => {
+			let half = (2.0f32).powi(i32::from(bits) - 1);
+			(-half, half - 1.0)
+		},
+		ValueRange::Bits(bits) => (0.0, (2.0f32).powi(i32::from(bits)) - 1.0),
+		ValueRange::MinMax(min, max) => (min, max),
+		ValueRange::Auto => auto_value_range(image),
+	};
+	if min == max {
+		(min, min + 1.0)
+	} else {
+		(min, max)
+	}
+}
+
+/// Compute the range of the color samples in an image.
+///
+/// The alpha channel and samples that are not finite are ignored.
+/// If there are no finite samples, or if all samples have the same value,
+/// the returned range still has a non-zero width.
+///
+/// For optical flow images, the range of the magnitude of the motion vectors is computed instead, starting at zero.
+fn auto_value_range(image: &ImageView) -> (f32, f32) {
+	let info = image.info();
+	let data = image.data();
+	let sample_type = info.pixel_format.sample_type();
+
+	if info.pixel_format == PixelFormat::Flow32F {
+		let mut max = 0.0f32;
+		for y in 0..info.height as usize {
+			for x in 0..info.width as usize {
+				let pixel = x * info.stride_x as usize + y * info.stride_y as usize;
+				let u = read_sample(data, pixel, sample_type);
+				let v = read_sample(data, pixel + info.stride_c as usize, sample_type);
+				if let (Some(u), Some(v)) = (u, v) {
+					let magnitude = u.hypot(v);
+					if magnitude.is_finite() {
+						max = max.max(magnitude);
+					}
+				}
+			}
+		}
+		return if max > 0.0 { (0.0, max) } else { (0.0, 1.0) };
+	}
+	let mut color_channels = usize::from(info.pixel_format.channels());
+	if info.pixel_format.alpha().is_some() {
+		color_channels -= 1;
+	}
+
+	// A depth of zero indicates an invalid measurement.
+	let skip_zero = info.pixel_format.is_depth();
+
+	let mut min = f32::INFINITY;
+	let mut max = f32::NEG_INFINITY;
+	for y in 0..info.height as usize {
+		for x in 0..info.width as usize {
+			let pixel = x * info.stride_x as usize + y * info.stride_y as usize;
+			for channel in 0..color_channels {
+				let value = match read_sample(data, pixel + channel * info.stride_c as usize, sample_type) {
+					Some(x) if x.is_finite() && !(skip_zero && x == 0.0) => x,
+					_ => continue,
+				};
+				min = min.min(value);
+				max = max.max(value);
+			}
+		}
+	}
+
+	if min > max {
+		(0.0, 1.0)
+	} else if min == max {
+		(min, min + 1.0)
+	} else {
+		(min, max)
+	}
+}
+
+/// Read a single sample from a byte slice in native endianness.
+///
+/// Returns [`None`] if the sample lies (partially) outside of the slice.
+pub(super) fn read_sample(data: &[u8], offset: usize, sample_type: SampleType) -> Option<f32> {
+	match sample_type {
+		SampleType::U8 => data.get(offset).map(|&x| f32::from(x)),
+		SampleType::U16 => {
+			let bytes = data.get(offset..offset + 2)?;
+			Some(f32::from(u16::from_ne_bytes([bytes[0], bytes[1]])))
+		},
+		SampleType::U32 => {
+			let bytes = data.get(offset..offset + 4)?;
+			Some(u32::from_ne_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as f32)
+		},
+		SampleType::I8 => data.get(offset).map(|&x| f32::from(x as i8)),
+		SampleType::I16 => {
+			let bytes = data.get(offset..offset + 2)?;
+			Some(f32::from(i16::from_ne_bytes([bytes[0], bytes[1]])))
+		},
+		SampleType::I32 => {
+			let bytes = data.get(offset..offset + 4)?;
+			Some(i32::from_ne_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]) as f32)
+		},
+		SampleType::F16 => {
+			let bytes = data.get(offset..offset + 2)?;
+			Some(f16_to_f32(u16::from_ne_bytes([bytes[0], bytes[1]])))
+		},
+		SampleType::F32 => {
+			let bytes = data.get(offset..offset + 4)?;
+			Some(f32::from_ne_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+		},
+	}
+}
+
+/// Convert the bits of an IEEE 754 half precision float to a single precision float.
+fn f16_to_f32(bits: u16) -> f32 {
+	let sign = if bits & 0x8000 == 0 { 1.0 } else { -1.0 };
+	let exponent = i32::from(bits >> 10 & 0x1F);
+	let mantissa = f32::from(bits & 0x3FF);
+
+	if exponent == 0 {
+		// Zero or subnormal number.
+		sign * mantissa * (2.0f32).powi(-24)
+	} else if exponent == 0x1F {
+		if mantissa == 0.0 {
+			sign * f32::INFINITY
+		} else {
+			f32::NAN
+		}
+	} else {
+		sign * (1.0 + mantissa / 1024.0) * (2.0f32).powi(exponent - 15)
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use assert2::assert;
+	use crate::ImageInfo;
+
+	#[test]
+	fn f16_to_f32() {
+		assert!(super::f16_to_f32(0x0000) == 0.0);
+		assert!(super::f16_to_f32(0x3C00) == 1.0);
+		assert!(super::f16_to_f32(0xC000) == -2.0);
+		assert!(super::f16_to_f32(0x3555) == 0.333_251_95);
+		assert!(super::f16_to_f32(0x7BFF) == 65504.0);
+		assert!(super::f16_to_f32(0x0001) == (2.0f32).powi(-24));
+		assert!(super::f16_to_f32(0x7C00) == f32::INFINITY);
+		assert!(super::f16_to_f32(0x7E00).is_nan());
+	}
+
+	#[test]
+	fn value_range() {
+		let data = [0u8; 4];
+		let image = |value_range| ImageView::new(ImageInfo { value_range, ..ImageInfo::mono8(2, 2) }, &data);
+		assert!(super::value_range(&image(ValueRange::Full), 0.0, 255.0) == (0.0, 255.0));
+		assert!(super::value_range(&image(ValueRange::Bits(4)), 0.0, 255.0) == (0.0, 15.0));
+		assert!(super::value_range(&image(ValueRange::MinMax(10.0, 20.0)), 0.0, 255.0) == (10.0, 20.0));
+		assert!(super::value_range(&image(ValueRange::Full), -128.0, 127.0) == (-128.0, 127.0));
+		assert!(super::value_range(&image(ValueRange::Bits(4)), -128.0, 127.0) == (-8.0, 7.0));
+
+		// Empty ranges are widened.
+		assert!(super::value_range(&image(ValueRange::Bits(0)), 0.0, 255.0) == (0.0, 1.0));
+		assert!(super::value_range(&image(ValueRange::MinMax(3.0, 3.0)), 0.0, 255.0) == (3.0, 4.0));
+		assert!(super::value_range(&image(ValueRange::Bits(0)), -128.0, 127.0) == (-0.5, 0.5));
+	}
+
+	#[test]
+	fn auto_value_range() {
+		let data: Vec<u8> = [0.5f32, f32::NAN, -2.0, 7.0, 1.0, f32::INFINITY].iter().flat_map(|x| x.to_ne_bytes().to_vec()).collect();
+		assert!(super::auto_value_range(&ImageView::new(ImageInfo::mono32f(3, 2), &data)) == (-2.0, 7.0));
+		assert!(super::auto_value_range(&ImageView::new(ImageInfo::rgb32f(2, 1), &data)) == (-2.0, 7.0));
+		assert!(super::auto_value_range(&ImageView::new(ImageInfo::mono32f(1, 1), &data)) == (0.5, 1.5));
+
+		// The alpha channel should be ignored.
+		let data = [10, 20, 30, 255, 40, 50, 60, 0];
+		assert!(super::auto_value_range(&ImageView::new(ImageInfo::rgba8(2, 1), &data)) == (10.0, 60.0));
+		let info = ImageInfo::planar(crate::PixelFormat::Rgba8(crate::Alpha::Unpremultiplied), 2, 1);
+		let data = [10, 20, 30, 40, 50, 60, 255, 0];
+		assert!(super::auto_value_range(&ImageView::new(info, &data)) == (10.0, 60.0));
+
+		// Invalid depth measurements should be ignored.
+		let data: Vec<u8> = [0u16, 1500, 700, 0].iter().flat_map(|x| x.to_ne_bytes().to_vec()).collect();
+		assert!(super::auto_value_range(&ImageView::new(ImageInfo::depth16(2, 2), &data)) == (700.0, 1500.0));
+		assert!(super::auto_value_range(&ImageView::new(ImageInfo::mono16(2, 2), &data)) == (0.0, 1500.0));
+
+		// Flow images use the magnitude of the motion vectors.
+		let data: Vec<u8> = [3.0f32, -4.0, f32::NAN, 10.0].iter().flat_map(|x| x.to_ne_bytes().to_vec()).collect();
+		assert!(super::auto_value_range(&ImageView::new(ImageInfo::flow32f(2, 1), &data)) == (0.0, 5.0));
+	}
 }
@@ -1,7 +1,9 @@
 mod buffer;
 mod gpu_image;
 mod map_buffer;
+mod readout;
 mod retain_mut;
+mod statistics;
 mod uniforms_buffer;
 
 pub use buffer::create_buffer_with_value;
@@ -9,5 +11,7 @@ pub use gpu_image::GpuImage;
 pub use gpu_image::GpuImageUniforms;
 pub use map_buffer::map_buffer;
 pub use map_buffer::map_buffer_mut;
+pub use readout::pixel_readout;
 pub use retain_mut::RetainMut;
+pub use statistics::ImageStatistics;
 pub use uniforms_buffer::UniformsBuffer;
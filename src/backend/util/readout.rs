@@ -0,0 +1,126 @@
+use crate::image_info::SampleType;
+use crate::ImageInfo;
+use crate::PixelFormat;
+use super::gpu_image::read_sample;
+
+/// Describe the value of the pixel at `(x, y)` in a human readable form.
+///
+/// The position is in image coordinates, so any flip of the data in memory is taken into account.
+///
+/// Label images report the label ID, depth images report the depth in metres,
+/// YUV images report the luma and chroma samples,
+/// and all other images report the raw value of each sample.
+///
+/// Returns [`None`] if the pixel lies outside of the image or the data.
+pub fn pixel_readout(info: &ImageInfo, data: &[u8], x: u32, y: u32) -> Option<String> {
+	let (x, y) = info.memory_position(x, y)?;
+	let sample_type = info.pixel_format.sample_type();
+	let pixel = x as usize * info.stride_x as usize + y as usize * info.stride_y as usize;
+
+	if info.pixel_format.is_label() {
+		return Some(format!("label {}", read_uint(data, pixel, sample_type)?));
+	}
+
+	if info.pixel_format.is_depth() {
+		let depth = read_sample(data, pixel, sample_type)?;
+		if depth == 0.0 || depth.is_nan() {
+			return Some(String::from("invalid depth"));
+		}
+		let metres = match info.pixel_format {
+			PixelFormat::Depth16 => depth / 1000.0,
+			_ => depth,
+		};
+		return Some(format!("{:.3} m", metres));
+	}
+
+	if info.pixel_format.yuv_encoding().is_some() {
+		let luma = match info.pixel_format {
+			PixelFormat::Uyvy(_) => pixel + 1,
+			_ => pixel,
+		};
+		let (chroma_x, chroma_y) = match info.pixel_format {
+			PixelFormat::Yuyv(_) | PixelFormat::Uyvy(_) => (x / 2, y),
+			_ => (x / 2, y / 2),
+		};
+		let chroma = chroma_x as usize * info.chroma.stride_x as usize + chroma_y as usize * info.chroma.stride_y as usize;
+		let y = data.get(luma)?;
+		let u = data.get(info.chroma.offset_u as usize + chroma)?;
+		let v = data.get(info.chroma.offset_v as usize + chroma)?;
+		return Some(format!("Y {}, U {}, V {}", y, u, v));
+	}
+
+	let mut samples = Vec::with_capacity(usize::from(info.pixel_format.channels()));
+	for channel in 0..usize::from(info.pixel_format.channels()) {
+		let value = read_sample(data, pixel + channel * info.stride_c as usize, sample_type)?;
+		samples.push(value.to_string());
+	}
+
+	if samples.len() == 1 {
+		Some(samples.remove(0))
+	} else {
+		Some(format!("[{}]", samples.join(", ")))
+	}
+}
+
+/// Read a single unsigned integer sample from a byte slice in native endianness.
+///
+/// Returns [`None`] if the sample lies (partially) outside of the slice or is not an unsigned integer.
+fn read_uint(data: &[u8], offset: usize, sample_type: SampleType) -> Option<u32> {
+	match sample_type {
+		SampleType::U8 => data.get(offset).map(|&x| u32::from(x)),
+		SampleType::U16 => {
+			let bytes = data.get(offset..offset + 2)?;
+			Some(u32::from(u16::from_ne_bytes([bytes[0], bytes[1]])))
+		},
+		SampleType::U32 => {
+			let bytes = data.get(offset..offset + 4)?;
+			Some(u32::from_ne_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+		},
+		SampleType::I8 | SampleType::I16 | SampleType::I32 => None,
+		SampleType::F16 | SampleType::F32 => None,
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use assert2::assert;
+
+	#[test]
+	fn readout_label() {
+		let data: Vec<u8> = [7u32, 70_000].iter().flat_map(|x| x.to_ne_bytes()).collect();
+		let info = ImageInfo::label32(2, 1);
+		assert!(pixel_readout(&info, &data, 0, 0).as_deref() == Some("label 7"));
+		assert!(pixel_readout(&info, &data, 1, 0).as_deref() == Some("label 70000"));
+		assert!(pixel_readout(&info, &data, 2, 0) == None);
+	}
+
+	#[test]
+	fn readout_depth() {
+		let data: Vec<u8> = [1234u16, 0].iter().flat_map(|x| x.to_ne_bytes()).collect();
+		assert!(pixel_readout(&ImageInfo::depth16(2, 1), &data, 0, 0).as_deref() == Some("1.234 m"));
+		assert!(pixel_readout(&ImageInfo::depth16(2, 1), &data, 1, 0).as_deref() == Some("invalid depth"));
+
+		let data: Vec<u8> = [2.5f32, f32::NAN].iter().flat_map(|x| x.to_ne_bytes()).collect();
+		assert!(pixel_readout(&ImageInfo::depth32f(2, 1), &data, 0, 0).as_deref() == Some("2.500 m"));
+		assert!(pixel_readout(&ImageInfo::depth32f(2, 1), &data, 1, 0).as_deref() == Some("invalid depth"));
+	}
+
+	#[test]
+	fn readout_samples() {
+		let data = [1u8, 2, 3, 4, 5, 6];
+		assert!(pixel_readout(&ImageInfo::rgb8(2, 1), &data, 1, 0).as_deref() == Some("[4, 5, 6]"));
+		assert!(pixel_readout(&ImageInfo::mono8(3, 2), &data, 2, 1).as_deref() == Some("6"));
+		assert!(pixel_readout(&ImageInfo::planar(PixelFormat::Rgb8, 2, 1), &data, 1, 0).as_deref() == Some("[2, 4, 6]"));
+		let info = ImageInfo { flip_x: true, flip_y: true, ..ImageInfo::mono8(3, 2) };
+		assert!(pixel_readout(&info, &data, 2, 1).as_deref() == Some("1"));
+
+		let data: Vec<u8> = [-3i16, 200].iter().flat_map(|x| x.to_ne_bytes()).collect();
+		assert!(pixel_readout(&ImageInfo::mono16i(2, 1), &data, 0, 0).as_deref() == Some("-3"));
+		assert!(pixel_readout(&ImageInfo::mono8i(2, 1), &[0xFF, 0x7F], 0, 0).as_deref() == Some("-1"));
+		assert!(pixel_readout(&ImageInfo::mono8i(2, 1), &[0xFF, 0x7F], 1, 0).as_deref() == Some("127"));
+
+		// Data that is too short gives no readout.
+		assert!(pixel_readout(&ImageInfo::rgb8(2, 2), &data, 1, 1) == None);
+	}
+}
@@ -0,0 +1,214 @@
+use crate::ImageInfo;
+use crate::PixelFormat;
+use super::gpu_image::read_sample;
+
+/// The number of bins in the histogram of an image.
+const HISTOGRAM_BINS: usize = 1024;
+
+/// The number of entries in a histogram equalization lookup table.
+pub const EQUALIZATION_LUT_SIZE: usize = 256;
+
+/// Statistics of the color samples of an image, used for contrast enhancement.
+///
+/// All values are normalized: the value range of the image is mapped to `[0, 1]`,
+/// just like the fragment shader does before applying the contrast enhancement.
+#[derive(Debug, Clone)]
+pub struct ImageStatistics {
+	/// The smallest normalized sample value.
+	min: f32,
+
+	/// The largest normalized sample value.
+	max: f32,
+
+	/// Histogram of the normalized sample values, with bins evenly spaced between `min` and `max`.
+	histogram: Vec<u64>,
+
+	/// The total number of samples in the histogram.
+	total: u64,
+}
+
+impl ImageStatistics {
+	/// Compute the statistics of the color samples of an image.
+	///
+	/// The alpha channel and samples that are not finite are ignored, as are invalid measurements in depth images.
+	///
+	/// Returns [`None`] if contrast enhancement is not supported for the pixel format,
+	/// or if the image has no valid samples.
+	pub fn compute(info: &ImageInfo, data: &[u8], value_range: (f32, f32)) -> Option<Self> {
+		if !supports_contrast(info.pixel_format) {
+			return None;
+		}
+
+		let (value_min, value_max) = value_range;
+		let skip_zero = info.pixel_format.is_depth();
+		let sample_type = info.pixel_format.sample_type();
+		let mut color_channels = usize::from(info.pixel_format.channels());
+		if info.pixel_format.alpha().is_some() {
+			color_channels -= 1;
+		}
+
+		let samples = || {
+			(0..info.height as usize).flat_map(move |y| {
+				(0..info.width as usize).flat_map(move |x| {
+					let pixel = x * info.stride_x as usize + y * info.stride_y as usize;
+					(0..color_channels).filter_map(move |channel| {
+						match read_sample(data, pixel + channel * info.stride_c as usize, sample_type) {
+							Some(x) if x.is_finite() && !(skip_zero && x == 0.0) => {
+								Some(((x - value_min) / (value_max - value_min)).clamp(0.0, 1.0))
+							},
+							_ => None,
+						}
+					})
+				})
+			})
+		};
+
+		let mut min = f32::INFINITY;
+		let mut max = f32::NEG_INFINITY;
+		for value in samples() {
+			min = min.min(value);
+			max = max.max(value);
+		}
+		if min > max {
+			return None;
+		}
+
+		let mut histogram = vec![0; HISTOGRAM_BINS];
+		let mut total = 0;
+		for value in samples() {
+			histogram[bin_index(value, min, max)] += 1;
+			total += 1;
+		}
+
+		Some(Self { min, max, histogram, total })
+	}
+
+	/// Get the range between the smallest and largest normalized sample value.
+	pub fn min_max(&self) -> (f32, f32) {
+		(self.min, self.max)
+	}
+
+	/// Get the normalized sample range after clipping `percentile` percent of the samples at both ends.
+	pub fn percentile_range(&self, percentile: f32) -> (f32, f32) {
+		let clipped = (f64::from(percentile.clamp(0.0, 50.0)) / 100.0 * self.total as f64) as u64;
+
+		let mut low = 0;
+		let mut count = 0;
+		for (i, &bin) in self.histogram.iter().enumerate() {
+			count += bin;
+			if count > clipped {
+				low = i;
+				break;
+			}
+		}
+
+		let mut high = self.histogram.len() - 1;
+		let mut count = 0;
+		for (i, &bin) in self.histogram.iter().enumerate().rev() {
+			count += bin;
+			if count > clipped {
+				high = i;
+				break;
+			}
+		}
+
+		let high = high.max(low);
+		(self.bin_start(low), self.bin_start(high + 1))
+	}
+
+	/// Compute a histogram equalization lookup table.
+	///
+	/// The table maps values evenly spaced between the smallest and largest normalized sample value
+	/// to the fraction of samples at or below that value.
+	pub fn equalization_lut(&self) -> [f32; EQUALIZATION_LUT_SIZE] {
+		let mut cumulative = Vec::with_capacity(self.histogram.len());
+		let mut count = 0;
+		for &bin in &self.histogram {
+			count += bin;
+			cumulative.push(count);
+		}
+
+		let mut lut = [0.0; EQUALIZATION_LUT_SIZE];
+		for (i, entry) in lut.iter_mut().enumerate() {
+			let bin = i * (HISTOGRAM_BINS - 1) / (EQUALIZATION_LUT_SIZE - 1);
+			*entry = (cumulative[bin] as f64 / self.total as f64) as f32;
+		}
+		lut
+	}
+
+	/// Get the normalized value at the start of a histogram bin.
+	fn bin_start(&self, bin: usize) -> f32 {
+		self.min + (self.max - self.min) * bin as f32 / HISTOGRAM_BINS as f32
+	}
+}
+
+/// Check if contrast enhancement is supported for a pixel format.
+///
+/// Only formats that map each sample linearly to a display value are supported.
+pub(super) fn supports_contrast(pixel_format: PixelFormat) -> bool {
+	!matches!(
+		pixel_format,
+		PixelFormat::Nv12(_)
+			| PixelFormat::Nv21(_)
+			| PixelFormat::I420(_)
+			| PixelFormat::Yuyv(_)
+			| PixelFormat::Uyvy(_)
+			| PixelFormat::Label8
+			| PixelFormat::Label16
+			| PixelFormat::Label32
+			| PixelFormat::Indexed8
+			| PixelFormat::Flow32F
+	)
+}
+
+/// Get the histogram bin for a value in the range `[min, max]`.
+fn bin_index(value: f32, min: f32, max: f32) -> usize {
+	if max <= min {
+		return 0;
+	}
+	let bin = ((value - min) / (max - min) * HISTOGRAM_BINS as f32) as usize;
+	bin.min(HISTOGRAM_BINS - 1)
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use assert2::assert;
+
+	#[test]
+	fn min_max() {
+		let data = [10u8, 20, 30, 40, 50, 60];
+		let statistics = ImageStatistics::compute(&ImageInfo::mono8(3, 2), &data, (0.0, 100.0)).unwrap();
+		assert!(statistics.min_max() == (0.1, 0.6));
+
+		// Invalid depth measurements are ignored.
+		let data: Vec<u8> = [0u16, 500, 1000, 0].iter().flat_map(|x| x.to_ne_bytes()).collect();
+		let statistics = ImageStatistics::compute(&ImageInfo::depth16(2, 2), &data, (0.0, 1000.0)).unwrap();
+		assert!(statistics.min_max() == (0.5, 1.0));
+
+		// Label images are not supported.
+		assert!(let None = ImageStatistics::compute(&ImageInfo::label8(3, 2), &[1, 2, 3, 4, 5, 6], (0.0, 255.0)));
+	}
+
+	#[test]
+	fn percentile_range() {
+		let data: Vec<u8> = (0..100).collect();
+		let statistics = ImageStatistics::compute(&ImageInfo::mono8(10, 10), &data, (0.0, 99.0)).unwrap();
+		assert!(statistics.percentile_range(0.0) == (0.0, 1.0));
+
+		let (low, high) = statistics.percentile_range(10.0);
+		assert!((low - 0.1).abs() < 0.01);
+		assert!((high - 0.9).abs() < 0.01);
+	}
+
+	#[test]
+	fn equalization_lut() {
+		// Most samples are dark, so equalization should stretch the dark values.
+		let data = [0u8, 1, 2, 3, 4, 5, 6, 255];
+		let statistics = ImageStatistics::compute(&ImageInfo::mono8(8, 1), &data, (0.0, 255.0)).unwrap();
+		let lut = statistics.equalization_lut();
+		assert!(lut[0] == 0.125);
+		assert!(lut[7] == 0.875);
+		assert!(lut[EQUALIZATION_LUT_SIZE - 1] == 1.0);
+	}
+}
@@ -1,4 +1,6 @@
+use crate::backend::util::pixel_readout;
 use crate::backend::util::GpuImage;
+use crate::backend::util::ImageStatistics;
 use crate::backend::util::UniformsBuffer;
 use crate::error::InvalidWindowId;
 use crate::error::SetImageError;
@@ -20,6 +22,9 @@ pub struct Window {
 	/// The winit window.
 	pub window: winit::window::Window,
 
+	/// The window title, without the pixel readout.
+	pub title: String,
+
 	/// The window options.
 	pub options: WindowOptions,
 
@@ -32,9 +37,24 @@ pub struct Window {
 	/// The window specific uniforms for the render pipeline.
 	pub uniforms: UniformsBuffer<WindowUniforms>,
 
+	/// The window specific uniforms for rendering overlays.
+	///
+	/// These are updated together with [`Self::uniforms`].
+	pub overlay_uniforms: UniformsBuffer<WindowUniforms>,
+
 	/// The image to display (if any).
 	pub image: Option<GpuImage>,
 
+	/// A copy of the image data, used for the pixel readout and contrast enhancement.
+	///
+	/// The copy is only kept while one of those options is enabled.
+	pub image_data: Option<Box<[u8]>>,
+
+	/// Statistics of the image, used for contrast enhancement.
+	///
+	/// The statistics are only computed when a contrast enhancement mode is active.
+	pub statistics: Option<ImageStatistics>,
+
 	/// The zoom of the image.
 	pub zoom: f32,
 
@@ -104,6 +124,11 @@ impl<'a> WindowHandle<'a> {
 		self.context_handle.set_window_visible(self.window_id, visible)
 	}
 
+	/// Get the options of the window.
+	pub fn options(&self) -> Result<&WindowOptions, InvalidWindowId> {
+		self.context_handle.window_options(self.window_id)
+	}
+
 	/// Change the options of the window.
 	pub fn set_options<F>(&mut self, make_options: F) -> Result<(), InvalidWindowId>
 	where
@@ -112,6 +137,88 @@ impl<'a> WindowHandle<'a> {
 		self.context_handle.set_window_options(self.window_id, make_options)
 	}
 
+	/// Get the display range of the window.
+	pub fn display_range(&self) -> Result<DisplayRange, InvalidWindowId> {
+		Ok(self.options()?.display_range)
+	}
+
+	/// Set the display range of the window.
+	///
+	/// See [`DisplayRange`] for details.
+	pub fn set_display_range(&mut self, display_range: DisplayRange) -> Result<(), InvalidWindowId> {
+		self.set_options(|options| options.clone().set_display_range(display_range))
+	}
+
+	/// Get the rotation and mirroring of the image in the window.
+	pub fn view_transform(&self) -> Result<ViewTransform, InvalidWindowId> {
+		Ok(self.options()?.view_transform)
+	}
+
+	/// Set the rotation and mirroring of the image in the window.
+	///
+	/// See [`ViewTransform`] for details.
+	pub fn set_view_transform(&mut self, view_transform: ViewTransform) -> Result<(), InvalidWindowId> {
+		self.set_options(|options| options.clone().set_view_transform(view_transform))
+	}
+
+	/// Map a position in window coordinates to image coordinates.
+	///
+	/// Window coordinates are in physical pixels, relative to the top left corner of the window.
+	/// Image coordinates are in pixels, with the center of the top left pixel at (0, 0).
+	/// The conversion takes the zoom, pan, rotation and mirroring of the image into account.
+	/// The returned position may lie outside of the image.
+	///
+	/// Returns [`None`] if the window has no image.
+	pub fn window_to_image(&self, position: [f64; 2]) -> Result<Option<[f64; 2]>, InvalidWindowId> {
+		self.context_handle.window_to_image(self.window_id, position)
+	}
+
+	/// Map a position in image coordinates to window coordinates.
+	///
+	/// This is the inverse of [`Self::window_to_image`].
+	/// The returned position may lie outside of the window.
+	///
+	/// Returns [`None`] if the window has no image.
+	pub fn image_to_window(&self, position: [f64; 2]) -> Result<Option<[f64; 2]>, InvalidWindowId> {
+		self.context_handle.image_to_window(self.window_id, position)
+	}
+
+	/// Get the current zoom and pan of the window.
+	///
+	/// Returns [`None`] if the window has no image.
+	pub fn view(&self) -> Result<Option<ViewState>, InvalidWindowId> {
+		self.context_handle.window_view(self.window_id)
+	}
+
+	/// Set the zoom and pan of the window.
+	///
+	/// See [`ViewState`] for details.
+	pub fn set_view(&mut self, view: ViewState) -> Result<(), InvalidWindowId> {
+		self.context_handle.set_window_view(self.window_id, view)
+	}
+
+	/// Reset the zoom and pan of the window so that the image fits the window.
+	pub fn zoom_to_fit(&mut self) -> Result<(), InvalidWindowId> {
+		self.context_handle.zoom_window_to_fit(self.window_id)
+	}
+
+	/// Zoom the window around its center so that one image pixel covers one window pixel.
+	pub fn zoom_to_actual_pixels(&mut self) -> Result<(), InvalidWindowId> {
+		self.context_handle.zoom_window_to_actual_pixels(self.window_id)
+	}
+
+	/// Pan the window so that a position in image coordinates is shown at the center of the window.
+	///
+	/// Image coordinates are in pixels, with the center of the top left pixel at (0, 0).
+	pub fn center_on(&mut self, position: [f64; 2]) -> Result<(), InvalidWindowId> {
+		self.context_handle.center_window_on(self.window_id, position)
+	}
+
+	/// Zoom and pan the window so that a rectangle of image pixels fills the window as much as possible.
+	pub fn zoom_to_rect(&mut self, rect: Rectangle) -> Result<(), InvalidWindowId> {
+		self.context_handle.zoom_window_to_rect(self.window_id, rect)
+	}
+
 	/// Set the image to display on the window.
 	pub fn set_image(&mut self, name: impl Into<String>, image: &impl AsImageView) -> Result<(), SetImageError> {
 		self.context_handle.set_window_image(self.window_id, name, image)
@@ -172,6 +279,413 @@ pub struct WindowOptions {
 	///
 	/// Defaults to true.
 	pub show_overlays: bool,
+
+	/// If true, show Bayer images as raw grayscale mosaic instead of demosaicing them.
+	///
+	/// Defaults to false.
+	pub show_raw_bayer: bool,
+
+	/// Colors to use for label images, overriding the automatic palette.
+	///
+	/// Label `i` is shown with color `i` of the palette.
+	/// Labels outside of the palette use the automatic palette.
+	/// Only the first 256 entries of the palette are used, and they are shown with 8 bits per channel.
+	///
+	/// Defaults to `None`.
+	pub label_palette: Option<Vec<Color>>,
+
+	/// If true, show the value of the pixel under the mouse cursor in the window title.
+	///
+	/// Defaults to false.
+	pub show_pixel_readout: bool,
+
+	/// The color used to show invalid measurements in depth images.
+	///
+	/// Defaults to magenta, which does not appear in the colormap used for depth images.
+	pub invalid_depth_color: Color,
+
+	/// Draw arrows over optical flow images, one for each cell of a grid with the given spacing in image pixels.
+	///
+	/// The arrows are scaled such that the upper bound of the value range of the image fills a grid cell.
+	///
+	/// Defaults to `None`.
+	pub flow_arrow_spacing: Option<u32>,
+
+	/// If true, show monochrome images without alpha channel with a diverging blue-white-red colormap centered on zero.
+	///
+	/// The bound of the value range that is furthest from zero is shown fully saturated.
+	/// This is useful for signed data such as difference images, gradients and filter responses.
+	///
+	/// Defaults to false.
+	pub diverging: bool,
+
+	/// The colormap used to show monochrome images.
+	///
+	/// Depth images are shown with [`Colormap::Turbo`] unless a different colormap than [`Colormap::Gray`] is selected.
+	///
+	/// The colormap can be cycled by pressing C in the window.
+	///
+	/// Defaults to [`Colormap::Gray`].
+	pub colormap: Colormap,
+
+	/// The display range applied to the colors of the image.
+	///
+	/// The display range can be adjusted by dragging with the right mouse button in the window.
+	/// Dragging horizontally changes the width of the range, dragging vertically moves the center of the range.
+	///
+	/// Defaults to the identity mapping.
+	pub display_range: DisplayRange,
+
+	/// Automatic contrast enhancement based on the statistics of the image.
+	///
+	/// The statistics are computed when the image is set, the image data itself is not modified.
+	/// Contrast enhancement is not applied to YUV, label, indexed and optical flow images.
+	///
+	/// The mode can be cycled by pressing E in the window.
+	///
+	/// Defaults to [`ContrastMode::Normal`].
+	pub contrast_mode: ContrastMode,
+
+	/// The percentage of samples to clip at both ends of the range for [`ContrastMode::Percentile`].
+	///
+	/// Defaults to 1.0.
+	pub contrast_percentile: f32,
+
+	/// Show only a single channel of the image as grayscale.
+	///
+	/// The channel is selected after the image data has been decoded to RGBA,
+	/// so [`Channel::Red`] always shows the red channel, regardless of the order of the channels in memory.
+	/// Images without alpha channel have a fully opaque alpha channel.
+	///
+	/// The channel can be toggled by pressing R, G, B or A in the window.
+	///
+	/// Defaults to `None`.
+	pub channel: Option<Channel>,
+
+	/// If true, the display options can be changed with single key shortcuts.
+	///
+	/// C cycles the colormap, E the contrast enhancement and F the sampling filter,
+	/// R, G, B and A show a single channel, `[` and `]` rotate the image and X and Y mirror it.
+	///
+	/// Disable this if the application uses these keys itself.
+	///
+	/// Defaults to true.
+	pub display_shortcuts: bool,
+
+	/// Draw a checkerboard behind the image to make transparent regions visible.
+	///
+	/// The checkerboard is not drawn behind overlays.
+	///
+	/// Defaults to `None`.
+	pub checkerboard: Option<Checkerboard>,
+
+	/// The filter used to sample the image when it is scaled.
+	///
+	/// The filter can be cycled by pressing F in the window.
+	///
+	/// Defaults to [`Filter::Nearest`].
+	pub filter: Filter,
+
+	/// If true, draw a grid between image pixels when zoomed in far enough.
+	///
+	/// The grid is drawn when an image pixel covers at least 8 by 8 window pixels.
+	///
+	/// Defaults to true.
+	pub show_pixel_grid: bool,
+
+	/// If true, draw the values of each image pixel inside the pixel when zoomed in far enough for the text to be readable.
+	///
+	/// The values are the raw samples of the pixel, in the order of the channels in memory.
+	///
+	/// Defaults to true.
+	pub show_pixel_values: bool,
+
+	/// The rotation and mirroring of the image in the window.
+	///
+	/// The image can be rotated in steps of 90 degrees by pressing `[` and `]` in the window,
+	/// and mirrored by pressing X or Y.
+	///
+	/// Defaults to no rotation and no mirroring.
+	pub view_transform: ViewTransform,
+
+	/// If true, the image can be zoomed and panned with the keyboard.
+	///
+	/// The arrow keys or H, J, K and L pan the image, + and - zoom around the center of the window,
+	/// 0 resets the zoom and pan to fit the image and 1 zooms to one window pixel per image pixel.
+	///
+	/// Defaults to true.
+	pub keyboard_navigation: bool,
+}
+
+/// A filter to sample scaled images with.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Filter {
+	/// Show each image pixel as a solid square.
+	Nearest,
+
+	/// Interpolate linearly between the four nearest image pixels.
+	///
+	/// This gives a smooth view when zoomed in.
+	Bilinear,
+
+	/// Average all image pixels covered by a window pixel.
+	///
+	/// This avoids aliasing when zoomed out, and behaves like [`Filter::Nearest`] when zoomed in.
+	Area,
+}
+
+impl Filter {
+	/// Get the next filter, wrapping around after the last one.
+	pub(crate) fn next(self) -> Self {
+		match self {
+			Filter::Nearest => Filter::Bilinear,
+			Filter::Bilinear => Filter::Area,
+			Filter::Area => Filter::Nearest,
+		}
+	}
+}
+
+/// A checkerboard pattern drawn behind transparent regions of an image.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Checkerboard {
+	/// The size of the squares in pixels.
+	///
+	/// For windows the size is in window pixels, for saved images it is in image pixels.
+	pub size: u32,
+
+	/// The colors of the squares.
+	pub colors: [Color; 2],
+
+	/// If true, also draw the checkerboard in images saved with the built-in save shortcuts.
+	pub in_saved_images: bool,
+}
+
+impl Default for Checkerboard {
+	fn default() -> Self {
+		Self {
+			size: 8,
+			colors: [Color::rgb(0.8, 0.8, 0.8), Color::rgb(0.6, 0.6, 0.6)],
+			in_saved_images: false,
+		}
+	}
+}
+
+/// A color channel of an image.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Channel {
+	/// The red channel.
+	Red,
+
+	/// The green channel.
+	Green,
+
+	/// The blue channel.
+	Blue,
+
+	/// The alpha channel.
+	Alpha,
+}
+
+/// Automatic contrast enhancement mode.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum ContrastMode {
+	/// Show the image using only the value range of the image info.
+	Normal,
+
+	/// Stretch the range between the smallest and largest sample in the image to the full display range.
+	MinMax,
+
+	/// Stretch the range between two percentiles of the samples to the full display range.
+	///
+	/// The percentage of samples to clip is set by [`WindowOptions::contrast_percentile`].
+	Percentile,
+
+	/// Equalize the histogram of the image, so that all display values are used equally often.
+	Equalize,
+}
+
+impl ContrastMode {
+	/// Get the next contrast mode, wrapping around after the last one.
+	pub(crate) fn next(self) -> Self {
+		match self {
+			ContrastMode::Normal => ContrastMode::MinMax,
+			ContrastMode::MinMax => ContrastMode::Percentile,
+			ContrastMode::Percentile => ContrastMode::Equalize,
+			ContrastMode::Equalize => ContrastMode::Normal,
+		}
+	}
+}
+
+/// Mapping of displayed color values to the final output color.
+///
+/// The mapping is applied to the red, green and blue channel of every pixel, after the image data has been converted to a color.
+/// It is not applied to the alpha channel.
+///
+/// Color values are normalized to the range `[0, 1]`.
+/// Values at or below the black point are shown as black, values at or above the white point are shown at full intensity.
+/// Values in between are mapped linearly to `[0, 1]` and then raised to the power `1 / gamma`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DisplayRange {
+	/// The color value that is shown as black.
+	pub black_point: f32,
+
+	/// The color value that is shown at full intensity.
+	pub white_point: f32,
+
+	/// The gamma correction to apply.
+	///
+	/// Values above 1 brighten the mid-tones, values below 1 darken them.
+	pub gamma: f32,
+}
+
+impl DisplayRange {
+	/// Create a new display range with the given black point, white point and gamma.
+	pub fn new(black_point: f32, white_point: f32, gamma: f32) -> Self {
+		Self { black_point, white_point, gamma }
+	}
+}
+
+impl Default for DisplayRange {
+	fn default() -> Self {
+		Self::new(0.0, 1.0, 1.0)
+	}
+}
+
+/// The zoom and pan of an image in a window.
+///
+/// The view state is independent of the size of the window,
+/// so it can be used to restore a view in a window that has been resized.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ViewState {
+	/// The zoom factor, relative to the image fitting the window.
+	pub zoom: f32,
+
+	/// The position in image coordinates that is shown at the center of the window.
+	///
+	/// Image coordinates are in pixels, with the center of the top left pixel at (0, 0).
+	pub center: [f64; 2],
+}
+
+/// The rotation and mirroring of an image in a window.
+///
+/// The image is mirrored first, and then rotated around its center.
+/// The rotated image is scaled to fit the window as a whole.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ViewTransform {
+	/// The clockwise rotation of the image in degrees.
+	pub rotation: f32,
+
+	/// Mirror the image horizontally, swapping the left and right side.
+	pub flip_horizontal: bool,
+
+	/// Mirror the image vertically, swapping the top and bottom side.
+	pub flip_vertical: bool,
+}
+
+impl ViewTransform {
+	/// Create a new view transform with the given clockwise rotation in degrees and mirroring.
+	pub fn new(rotation: f32, flip_horizontal: bool, flip_vertical: bool) -> Self {
+		Self { rotation, flip_horizontal, flip_vertical }
+	}
+
+	/// Rotate the view clockwise by the given number of degrees.
+	///
+	/// The resulting rotation is normalized to the range `[0, 360)`.
+	pub fn rotate(mut self, degrees: f32) -> Self {
+		self.rotation = (self.rotation + degrees).rem_euclid(360.0);
+		self
+	}
+
+	/// Get the sine and cosine of the rotation.
+	///
+	/// Rotations by a multiple of 90 degrees are exact.
+	fn sin_cos(&self) -> (f32, f32) {
+		let rotation = self.rotation.rem_euclid(360.0);
+		if rotation == 0.0 {
+			(0.0, 1.0)
+		} else if rotation == 90.0 {
+			(1.0, 0.0)
+		} else if rotation == 180.0 {
+			(0.0, -1.0)
+		} else if rotation == 270.0 {
+			(-1.0, 0.0)
+		} else {
+			rotation.to_radians().sin_cos()
+		}
+	}
+
+	/// Get the size of the bounding box of an image after applying the transform.
+	pub(crate) fn bounding_size(&self, image_size: [f32; 2]) -> [f32; 2] {
+		let (sin, cos) = self.sin_cos();
+		let [width, height] = image_size;
+		[
+			cos.abs() * width + sin.abs() * height,
+			sin.abs() * width + cos.abs() * height,
+		]
+	}
+
+	/// Get the columns of the matrix that maps image coordinates to coordinates in the bounding box of the transformed image.
+	///
+	/// Both coordinate systems are normalized to the range `[-0.5, 0.5]` with the Y axis pointing up.
+	pub(crate) fn matrix(&self, image_size: [f32; 2]) -> [[f32; 2]; 2] {
+		let (sin, cos) = self.sin_cos();
+		let [width, height] = image_size;
+		let [bounding_width, bounding_height] = self.bounding_size(image_size);
+		if bounding_width <= 0.0 || bounding_height <= 0.0 {
+			return [[1.0, 0.0], [0.0, 1.0]];
+		}
+
+		let x = if self.flip_horizontal { -width } else { width };
+		let y = if self.flip_vertical { -height } else { height };
+
+		// With the Y axis pointing up, a clockwise rotation maps (1, 0) to (cos, -sin) and (0, 1) to (sin, cos).
+		[
+			[cos * x / bounding_width, -sin * x / bounding_height],
+			[sin * y / bounding_width, cos * y / bounding_height],
+		]
+	}
+}
+
+impl Default for ViewTransform {
+	fn default() -> Self {
+		Self::new(0.0, false, false)
+	}
+}
+
+/// A colormap to show monochrome images with.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Colormap {
+	/// Plain grayscale.
+	Gray,
+
+	/// The perceptually uniform viridis colormap, going from dark blue over green to yellow.
+	Viridis,
+
+	/// The perceptually uniform magma colormap, going from black over purple to pale yellow.
+	Magma,
+
+	/// The perceptually uniform inferno colormap, going from black over red to bright yellow.
+	Inferno,
+
+	/// The turbo rainbow colormap, going from dark blue over green to dark red.
+	Turbo,
+
+	/// The classic jet rainbow colormap, going from dark blue over cyan and yellow to dark red.
+	Jet,
+}
+
+impl Colormap {
+	/// Get the next colormap, wrapping around after the last one.
+	pub(crate) fn next(self) -> Self {
+		match self {
+			Colormap::Gray => Colormap::Viridis,
+			Colormap::Viridis => Colormap::Magma,
+			Colormap::Magma => Colormap::Inferno,
+			Colormap::Inferno => Colormap::Turbo,
+			Colormap::Turbo => Colormap::Jet,
+			Colormap::Jet => Colormap::Gray,
+		}
+	}
 }
 
 impl Default for WindowOptions {
@@ -184,6 +698,24 @@ impl Default for WindowOptions {
 			resizable: true,
 			borderless: false,
 			show_overlays: true,
+			show_raw_bayer: false,
+			label_palette: None,
+			show_pixel_readout: false,
+			invalid_depth_color: Color::rgb(1.0, 0.0, 1.0),
+			flow_arrow_spacing: None,
+			diverging: false,
+			colormap: Colormap::Gray,
+			display_range: DisplayRange::default(),
+			contrast_mode: ContrastMode::Normal,
+			contrast_percentile: 1.0,
+			channel: None,
+			display_shortcuts: true,
+			checkerboard: None,
+			filter: Filter::Nearest,
+			show_pixel_grid: true,
+			show_pixel_values: true,
+			view_transform: ViewTransform::default(),
+			keyboard_navigation: true,
 		}
 	}
 }
@@ -246,6 +778,150 @@ impl WindowOptions {
 		self.show_overlays = show_overlays;
 		self
 	}
+
+	/// Set whether Bayer images should be shown as raw mosaic or demosaiced.
+	///
+	/// This function consumes and returns `self` to allow daisy chaining.
+	pub fn set_show_raw_bayer(mut self, show_raw_bayer: bool) -> Self {
+		self.show_raw_bayer = show_raw_bayer;
+		self
+	}
+
+	/// Set the colors to use for label images.
+	///
+	/// This function consumes and returns `self` to allow daisy chaining.
+	pub fn set_label_palette(mut self, label_palette: Vec<Color>) -> Self {
+		self.label_palette = Some(label_palette);
+		self
+	}
+
+	/// Set whether the value of the pixel under the mouse cursor should be shown in the window title.
+	///
+	/// This function consumes and returns `self` to allow daisy chaining.
+	pub fn set_show_pixel_readout(mut self, show_pixel_readout: bool) -> Self {
+		self.show_pixel_readout = show_pixel_readout;
+		self
+	}
+
+	/// Set the color used to show invalid measurements in depth images.
+	///
+	/// This function consumes and returns `self` to allow daisy chaining.
+	pub fn set_invalid_depth_color(mut self, invalid_depth_color: Color) -> Self {
+		self.invalid_depth_color = invalid_depth_color;
+		self
+	}
+
+	/// Set the grid spacing for the arrows drawn over optical flow images, or disable the arrows with `None`.
+	///
+	/// This function consumes and returns `self` to allow daisy chaining.
+	pub fn set_flow_arrow_spacing(mut self, flow_arrow_spacing: Option<u32>) -> Self {
+		self.flow_arrow_spacing = flow_arrow_spacing;
+		self
+	}
+
+	/// Set whether monochrome images should be shown with a diverging colormap centered on zero.
+	///
+	/// This function consumes and returns `self` to allow daisy chaining.
+	pub fn set_diverging(mut self, diverging: bool) -> Self {
+		self.diverging = diverging;
+		self
+	}
+
+	/// Set the colormap used to show monochrome images.
+	///
+	/// This function consumes and returns `self` to allow daisy chaining.
+	pub fn set_colormap(mut self, colormap: Colormap) -> Self {
+		self.colormap = colormap;
+		self
+	}
+
+	/// Set the display range applied to the colors of the image.
+	///
+	/// This function consumes and returns `self` to allow daisy chaining.
+	pub fn set_display_range(mut self, display_range: DisplayRange) -> Self {
+		self.display_range = display_range;
+		self
+	}
+
+	/// Set the automatic contrast enhancement mode.
+	///
+	/// This function consumes and returns `self` to allow daisy chaining.
+	pub fn set_contrast_mode(mut self, contrast_mode: ContrastMode) -> Self {
+		self.contrast_mode = contrast_mode;
+		self
+	}
+
+	/// Set the percentage of samples to clip at both ends of the range for [`ContrastMode::Percentile`].
+	///
+	/// This function consumes and returns `self` to allow daisy chaining.
+	pub fn set_contrast_percentile(mut self, contrast_percentile: f32) -> Self {
+		self.contrast_percentile = contrast_percentile;
+		self
+	}
+
+	/// Set the channel to show as grayscale, or show all channels with `None`.
+	///
+	/// This function consumes and returns `self` to allow daisy chaining.
+	pub fn set_channel(mut self, channel: Option<Channel>) -> Self {
+		self.channel = channel;
+		self
+	}
+
+	/// Set whether the display options can be changed with single key shortcuts.
+	///
+	/// This function consumes and returns `self` to allow daisy chaining.
+	pub fn set_display_shortcuts(mut self, display_shortcuts: bool) -> Self {
+		self.display_shortcuts = display_shortcuts;
+		self
+	}
+
+	/// Set the checkerboard to draw behind the image, or disable it with `None`.
+	///
+	/// This function consumes and returns `self` to allow daisy chaining.
+	pub fn set_checkerboard(mut self, checkerboard: Option<Checkerboard>) -> Self {
+		self.checkerboard = checkerboard;
+		self
+	}
+
+	/// Set the filter used to sample the image when it is scaled.
+	///
+	/// This function consumes and returns `self` to allow daisy chaining.
+	pub fn set_filter(mut self, filter: Filter) -> Self {
+		self.filter = filter;
+		self
+	}
+
+	/// Set whether a grid should be drawn between image pixels when zoomed in far enough.
+	///
+	/// This function consumes and returns `self` to allow daisy chaining.
+	pub fn set_show_pixel_grid(mut self, show_pixel_grid: bool) -> Self {
+		self.show_pixel_grid = show_pixel_grid;
+		self
+	}
+
+	/// Set whether the values of image pixels should be drawn when zoomed in far enough.
+	///
+	/// This function consumes and returns `self` to allow daisy chaining.
+	pub fn set_show_pixel_values(mut self, show_pixel_values: bool) -> Self {
+		self.show_pixel_values = show_pixel_values;
+		self
+	}
+
+	/// Set the rotation and mirroring of the image in the window.
+	///
+	/// This function consumes and returns `self` to allow daisy chaining.
+	pub fn set_view_transform(mut self, view_transform: ViewTransform) -> Self {
+		self.view_transform = view_transform;
+		self
+	}
+
+	/// Set whether the image can be zoomed and panned with the keyboard.
+	///
+	/// This function consumes and returns `self` to allow daisy chaining.
+	pub fn set_keyboard_navigation(mut self, keyboard_navigation: bool) -> Self {
+		self.keyboard_navigation = keyboard_navigation;
+		self
+	}
 }
 
 impl Window {
@@ -259,19 +935,225 @@ impl Window {
 		self.window.set_visible(visible);
 	}
 
+	/// Get the image pixel at a position in window coordinates.
+	///
+	/// Returns [`None`] if the window has no image or the position is outside of the image.
+	pub fn pixel_at(&self, position: winit::dpi::PhysicalPosition<f64>) -> Option<[u32; 2]> {
+		let info = self.image.as_ref()?.info();
+		let [x, y] = self.window_to_normalized([position.x, position.y])?;
+		if info.width == 0 || info.height == 0 || !(0.0..=1.0).contains(&x) || !(0.0..=1.0).contains(&y) {
+			return None;
+		}
+
+		// Use the same rounding as the fragment shader.
+		let x = (x * f64::from(info.width - 1)).round() as u32;
+		let y = ((1.0 - y) * f64::from(info.height - 1)).round() as u32;
+		Some([x, y])
+	}
+
+	/// Map a position in window coordinates to image coordinates.
+	///
+	/// Image coordinates are in pixels, with the center of the top left pixel at (0, 0).
+	/// The returned position may lie outside of the image.
+	///
+	/// Returns [`None`] if the window has no image.
+	pub fn window_to_image(&self, position: [f64; 2]) -> Option<[f64; 2]> {
+		let [scale_x, scale_y] = self.image_scale()?;
+		let [x, y] = self.window_to_normalized(position)?;
+		Some([x * scale_x, (1.0 - y) * scale_y])
+	}
+
+	/// Map a position in image coordinates to window coordinates.
+	///
+	/// Image coordinates are in pixels, with the center of the top left pixel at (0, 0).
+	/// The returned position may lie outside of the window.
+	///
+	/// Returns [`None`] if the window has no image.
+	pub fn image_to_window(&self, position: [f64; 2]) -> Option<[f64; 2]> {
+		let [scale_x, scale_y] = self.image_scale()?;
+		let normalized = [position[0] / scale_x, 1.0 - position[1] / scale_y];
+		self.calculate_uniforms().normalized_to_window(self.inner_size(), normalized)
+	}
+
+	/// Map a position in window coordinates to normalized image coordinates.
+	///
+	/// Normalized image coordinates go from (0, 0) at the bottom left of the image to (1, 1) at the top right.
+	fn window_to_normalized(&self, position: [f64; 2]) -> Option<[f64; 2]> {
+		self.calculate_uniforms().window_to_normalized(self.inner_size(), position)
+	}
+
+	/// Get the inner size of the window in physical pixels.
+	fn inner_size(&self) -> [f64; 2] {
+		let size = self.window.inner_size();
+		[f64::from(size.width), f64::from(size.height)]
+	}
+
+	/// Get the factors to convert normalized image coordinates to image coordinates.
+	///
+	/// This matches the texture coordinates computed by the vertex shader.
+	fn image_scale(&self) -> Option<[f64; 2]> {
+		let info = self.image.as_ref()?.info();
+		Some([f64::from(info.width.max(2) - 1), f64::from(info.height.max(2) - 1)])
+	}
+
+	/// Get the current zoom and pan of the image.
+	///
+	/// Returns [`None`] if the window has no image.
+	pub fn view(&self) -> Option<ViewState> {
+		let size = self.window.inner_size();
+		let center = self.window_to_image([f64::from(size.width) / 2.0, f64::from(size.height) / 2.0])?;
+		Some(ViewState { zoom: self.zoom, center })
+	}
+
+	/// Set the zoom and pan of the image.
+	///
+	/// If the window has no image, only the zoom is applied.
+	pub fn set_view(&mut self, view: ViewState) {
+		self.zoom = view.zoom;
+		self.translate = [0.0, 0.0];
+		self.center_on(view.center);
+	}
+
+	/// Reset the zoom and pan so that the image fits the window.
+	pub fn zoom_to_fit(&mut self) {
+		self.zoom = 1.0;
+		self.translate = [0.0, 0.0];
+	}
+
+	/// Zoom the image around the center of the window by a factor.
+	pub fn zoom_by(&mut self, factor: f32) {
+		match self.view() {
+			Some(view) => self.set_view(ViewState {
+				zoom: view.zoom * factor,
+				center: view.center,
+			}),
+			None => self.zoom *= factor,
+		}
+	}
+
+	/// Zoom the image around the center of the window so that one image pixel covers one window pixel.
+	///
+	/// If the aspect ratio of the image is not preserved, the horizontal axis of the image is used.
+	pub fn zoom_to_actual_pixels(&mut self) {
+		if let (Some(view), Some([axis_x, _])) = (self.view(), self.pixel_axes()) {
+			let scale = axis_x[0].hypot(axis_x[1]);
+			self.set_view(ViewState {
+				zoom: (f64::from(self.zoom) / scale) as f32,
+				center: view.center,
+			});
+		}
+	}
+
+	/// Pan the image so that a position in image coordinates is shown at the center of the window.
+	pub fn center_on(&mut self, position: [f64; 2]) {
+		let size = self.window.inner_size();
+		if let Some([x, y]) = self.image_to_window(position) {
+			let width = f64::from(size.width);
+			let height = f64::from(size.height);
+			self.translate[0] += ((width / 2.0 - x) / width) as f32;
+			// Positive image y-axis is equivalent to negative y-axis of the window, hence subtract.
+			self.translate[1] -= ((height / 2.0 - y) / height) as f32;
+		}
+	}
+
+	/// Zoom and pan the image so that a rectangle in image coordinates fills the window as much as possible.
+	pub fn zoom_to_rect(&mut self, rect: Rectangle) {
+		let [axis_x, axis_y] = match self.pixel_axes() {
+			Some(x) => x,
+			None => return,
+		};
+		let size = self.window.inner_size();
+		let width = f64::from(rect.width().max(1));
+		let height = f64::from(rect.height().max(1));
+
+		// The size of the rectangle in window pixels at the current zoom.
+		let extent_x = axis_x[0].abs() * width + axis_y[0].abs() * height;
+		let extent_y = axis_x[1].abs() * width + axis_y[1].abs() * height;
+		let scale = (f64::from(size.width) / extent_x).min(f64::from(size.height) / extent_y);
+
+		self.set_view(ViewState {
+			zoom: (f64::from(self.zoom) * scale) as f32,
+			center: [
+				f64::from(rect.x()) + width / 2.0 - 0.5,
+				f64::from(rect.y()) + height / 2.0 - 0.5,
+			],
+		});
+	}
+
+	/// Get the vectors in window coordinates that correspond to one pixel along the X and Y axis of the image.
+	fn pixel_axes(&self) -> Option<[[f64; 2]; 2]> {
+		let origin = self.image_to_window([0.0, 0.0])?;
+		let x = self.image_to_window([1.0, 0.0])?;
+		let y = self.image_to_window([0.0, 1.0])?;
+		Some([[x[0] - origin[0], x[1] - origin[1]], [y[0] - origin[0], y[1] - origin[1]]])
+	}
+
+	/// Get a human readable description of the pixel at a position in window coordinates.
+	pub fn pixel_readout(&self, position: winit::dpi::PhysicalPosition<f64>) -> Option<String> {
+		let [x, y] = self.pixel_at(position)?;
+		let info = self.image.as_ref()?.info();
+		let value = pixel_readout(info, self.image_data.as_deref()?, x, y)?;
+		Some(format!("({}, {}) = {}", x, y, value))
+	}
+
+	/// Check if the window options need a CPU side copy of the image data.
+	pub fn needs_image_data(&self) -> bool {
+		self.options.show_pixel_readout || self.options.contrast_mode != ContrastMode::Normal
+	}
+
+	/// Read the image data back from the GPU if the window options need it, or drop it if they don't.
+	pub fn update_image_data(&mut self, device: &wgpu::Device, queue: &wgpu::Queue) {
+		if !self.needs_image_data() {
+			self.image_data = None;
+		} else if self.image_data.is_none() {
+			self.image_data = self.image.as_ref().and_then(|image| image.read_data(device, queue).ok());
+		}
+	}
+
+	/// Upload the label palette from the window options to the image and overlays.
+	pub fn update_label_palette(&mut self, queue: &wgpu::Queue) {
+		let palette = self.options.label_palette.as_deref().unwrap_or(&[]);
+		for image in self.image.iter_mut().chain(self.overlays.iter_mut()) {
+			image.set_label_palette(queue, palette);
+		}
+	}
+
+	/// Compute the image statistics if they are needed for contrast enhancement and not available yet.
+	///
+	/// The histogram equalization lookup table is uploaded to the image together with the statistics.
+	pub fn update_statistics(&mut self, queue: &wgpu::Queue) {
+		if self.options.contrast_mode == ContrastMode::Normal || self.statistics.is_some() {
+			return;
+		}
+		if let (Some(image), Some(data)) = (&mut self.image, &self.image_data) {
+			self.statistics = ImageStatistics::compute(image.info(), data, image.value_range());
+			if let Some(statistics) = &self.statistics {
+				image.set_contrast_lut(queue, &statistics.equalization_lut());
+			}
+		}
+	}
+
 	/// Recalculate the uniforms for the render pipeline from the window state.
 	pub fn calculate_uniforms(&self) -> WindowUniforms {
 		if let Some(image) = &self.image {
 			let uniforms : WindowUniforms;
 			let image_size = [image.info().width as f32, image.info().height as f32];
+			let view_transform = &self.options.view_transform;
 			if !self.options.preserve_aspect_ratio {
 				uniforms = WindowUniforms::stretch(image_size);
 			} else {
 				let window_size = [self.window.inner_size().width as f32, self.window.inner_size().height as f32];
-				uniforms = WindowUniforms::fit(window_size, image_size);
+				// Fit the bounding box of the transformed image in the window,
+				// but keep the size of the image itself for the texture coordinates.
+				uniforms = WindowUniforms {
+					pixel_size: image_size,
+					..WindowUniforms::fit(window_size, view_transform.bounding_size(image_size))
+				};
 			}
+			let uniforms = uniforms.set_view_transform(view_transform);
 			let uniforms = uniforms.set_zoom(self.zoom);
-			uniforms.set_translation(self.translate)
+			let uniforms = uniforms.set_translation(self.translate);
+			uniforms.set_display_options(&self.options).set_contrast(&self.options, self.statistics.as_ref())
 		} else {
 			WindowUniforms::no_image()
 		}
@@ -294,6 +1176,66 @@ pub struct WindowUniforms {
 
 	/// The size of the image in pixels.
 	pub pixel_size: [f32; 2],
+
+	/// The first column of the matrix that rotates and mirrors the image within its area.
+	pub transform_x: [f32; 2],
+
+	/// The second column of the matrix that rotates and mirrors the image within its area.
+	pub transform_y: [f32; 2],
+
+	/// Non-zero to show Bayer images as raw mosaic instead of demosaicing them.
+	pub show_raw_bayer: u32,
+
+	/// The grid spacing for arrows over optical flow images, or zero to disable them.
+	pub flow_arrow_spacing: u32,
+
+	/// The color of invalid measurements in depth images.
+	pub invalid_depth_color: [f32; 4],
+
+	/// The colors of the checkerboard squares.
+	pub checkerboard_colors: [[f32; 4]; 2],
+
+	/// Non-zero to show monochrome images with a diverging colormap.
+	pub diverging: u32,
+
+	/// The colormap for monochrome images.
+	pub colormap: u32,
+
+	/// The color value that is shown as black.
+	pub black_point: f32,
+
+	/// The color value that is shown at full intensity.
+	pub white_point: f32,
+
+	/// The gamma correction applied to the colors.
+	pub gamma: f32,
+
+	/// The contrast enhancement: 0 for none, 1 for a linear stretch and 2 for histogram equalization.
+	pub contrast_mode: u32,
+
+	/// The normalized sample value that is stretched to black.
+	pub contrast_low: f32,
+
+	/// The normalized sample value that is stretched to full intensity.
+	pub contrast_high: f32,
+
+	/// The channel to show as grayscale: 0 for all channels, 1 to 4 for red, green, blue and alpha.
+	pub channel: u32,
+
+	/// The size of the checkerboard squares in pixels, or zero to disable the checkerboard.
+	pub checkerboard_size: u32,
+
+	/// The filter to sample the image with: 0 for nearest, 1 for bilinear and 2 for area.
+	pub sample_filter: u32,
+
+	/// Non-zero to draw a grid between image pixels when zoomed in.
+	pub show_pixel_grid: u32,
+
+	/// Non-zero to draw the values of image pixels when zoomed in.
+	pub show_pixel_values: u32,
+
+	/// Padding to round the size of the struct up to a multiple of 16 bytes, as required by the std140 layout.
+	pub _padding: [u32; 3],
 }
 
 impl WindowUniforms {
@@ -306,6 +1248,26 @@ impl WindowUniforms {
 			offset: [0.0; 2],
 			relative_size: [1.0; 2],
 			pixel_size,
+			transform_x: [1.0, 0.0],
+			transform_y: [0.0, 1.0],
+			show_raw_bayer: 0,
+			flow_arrow_spacing: 0,
+			invalid_depth_color: [1.0, 0.0, 1.0, 1.0],
+			checkerboard_colors: [[0.0; 4]; 2],
+			diverging: 0,
+			colormap: 0,
+			black_point: 0.0,
+			white_point: 1.0,
+			gamma: 1.0,
+			contrast_mode: 0,
+			contrast_low: 0.0,
+			contrast_high: 1.0,
+			channel: 0,
+			checkerboard_size: 0,
+			sample_filter: 0,
+			show_pixel_grid: 0,
+			show_pixel_values: 0,
+			_padding: [0; 3],
 		}
 	}
 
@@ -325,10 +1287,85 @@ impl WindowUniforms {
 		Self {
 			offset: [0.5 - 0.5 * w, 0.5 - 0.5 * h],
 			relative_size: [w, h],
-			pixel_size: image_size,
+			..Self::stretch(image_size)
 		}
 	}
 
+	/// Set the rotation and mirroring of the image.
+	///
+	/// The offset and relative size describe the bounding box of the transformed image.
+	pub fn set_view_transform(mut self, view_transform: &ViewTransform) -> Self {
+		let [transform_x, transform_y] = view_transform.matrix(self.pixel_size);
+		self.transform_x = transform_x;
+		self.transform_y = transform_y;
+		self
+	}
+
+	/// Map a position in window coordinates to normalized image coordinates.
+	///
+	/// Normalized image coordinates go from (0, 0) at the bottom left of the image to (1, 1) at the top right.
+	/// Returns [`None`] if the window is empty.
+	pub fn window_to_normalized(&self, window_size: [f64; 2], position: [f64; 2]) -> Option<[f64; 2]> {
+		let [width, height] = window_size;
+		if width <= 0.0 || height <= 0.0 {
+			return None;
+		}
+
+		// Normalized window coordinates have the origin at the bottom left,
+		// but the image data has the origin at the top left.
+		let x = (position[0] / width - f64::from(self.offset[0])) / f64::from(self.relative_size[0]);
+		let y = (1.0 - position[1] / height - f64::from(self.offset[1])) / f64::from(self.relative_size[1]);
+
+		// Undo the rotation and mirroring of the image.
+		let [[a, c], [b, d]] = [self.transform_x, self.transform_y];
+		let [a, b, c, d] = [f64::from(a), f64::from(b), f64::from(c), f64::from(d)];
+		let determinant = a * d - b * c;
+		let (x, y) = (x - 0.5, y - 0.5);
+		Some([(d * x - b * y) / determinant + 0.5, (a * y - c * x) / determinant + 0.5])
+	}
+
+	/// Map a position in normalized image coordinates to window coordinates.
+	///
+	/// This is the inverse of [`Self::window_to_normalized`].
+	/// Returns [`None`] if the window is empty.
+	pub fn normalized_to_window(&self, window_size: [f64; 2], position: [f64; 2]) -> Option<[f64; 2]> {
+		let [width, height] = window_size;
+		if width <= 0.0 || height <= 0.0 {
+			return None;
+		}
+
+		// Apply the rotation and mirroring of the image within its bounding box.
+		let [[a, c], [b, d]] = [self.transform_x, self.transform_y];
+		let [a, b, c, d] = [f64::from(a), f64::from(b), f64::from(c), f64::from(d)];
+		let (x, y) = (position[0] - 0.5, position[1] - 0.5);
+		let (x, y) = (a * x + b * y + 0.5, c * x + d * y + 0.5);
+
+		// Normalized window coordinates have the origin at the bottom left,
+		// but window coordinates have the origin at the top left.
+		let x = f64::from(self.offset[0]) + x * f64::from(self.relative_size[0]);
+		let y = f64::from(self.offset[1]) + y * f64::from(self.relative_size[1]);
+		Some([x * width, (1.0 - y) * height])
+	}
+
+	/// Get the area of the window covered by the image, in physical pixels.
+	///
+	/// The area has the origin at the top left of the window.
+	/// If the image is rotated, the area is the bounding box of the rotated image.
+	pub fn image_area(&self, window_size: [u32; 2]) -> Rectangle {
+		let [x, y] = self.offset;
+		let [width, height] = self.relative_size;
+		let [window_width, window_height] = [window_size[0] as f32, window_size[1] as f32];
+
+		// Normalized window coordinates have the origin at the bottom left,
+		// but the image area has the origin at the top left.
+		Rectangle::from_xywh(
+			(x * window_width) as i32,
+			((1.0 - y - height) * window_height) as i32,
+			(width * window_width) as u32,
+			(height * window_height) as u32,
+		)
+	}
+
 	/// Set the zoom of the image.
 	pub fn set_zoom(mut self, zoom: f32) -> Self {
 		self.relative_size = [zoom * self.relative_size[0], zoom * self.relative_size[1]] ;
@@ -342,4 +1379,207 @@ impl WindowUniforms {
 		self.offset = [self.offset[0] + translate[0], self.offset[1] + translate[1]];
 		self
 	}
+
+	/// Set the options that affect how the image data is displayed.
+	pub fn set_display_options(mut self, options: &WindowOptions) -> Self {
+		self.show_raw_bayer = u32::from(options.show_raw_bayer);
+		self.flow_arrow_spacing = options.flow_arrow_spacing.unwrap_or(0);
+		self.invalid_depth_color = color_to_vec4(&options.invalid_depth_color);
+		self.diverging = u32::from(options.diverging);
+		self.colormap = match options.colormap {
+			Colormap::Gray => 0,
+			Colormap::Viridis => 1,
+			Colormap::Magma => 2,
+			Colormap::Inferno => 3,
+			Colormap::Turbo => 4,
+			Colormap::Jet => 5,
+		};
+		self.black_point = options.display_range.black_point;
+		self.white_point = options.display_range.white_point;
+		self.gamma = options.display_range.gamma;
+		self.channel = match options.channel {
+			None => 0,
+			Some(Channel::Red) => 1,
+			Some(Channel::Green) => 2,
+			Some(Channel::Blue) => 3,
+			Some(Channel::Alpha) => 4,
+		};
+		if let Some(checkerboard) = &options.checkerboard {
+			self.checkerboard_size = checkerboard.size;
+			self.checkerboard_colors = [color_to_vec4(&checkerboard.colors[0]), color_to_vec4(&checkerboard.colors[1])];
+		} else {
+			self.checkerboard_size = 0;
+		}
+		self.sample_filter = match options.filter {
+			Filter::Nearest => 0,
+			Filter::Bilinear => 1,
+			Filter::Area => 2,
+		};
+		self.show_pixel_grid = u32::from(options.show_pixel_grid);
+		self.show_pixel_values = u32::from(options.show_pixel_values);
+		self
+	}
+
+	/// Get the uniforms to draw overlays with.
+	///
+	/// Overlays use the same position and size as the image, but none of the display options.
+	pub fn for_overlays(&self) -> Self {
+		Self {
+			offset: self.offset,
+			relative_size: self.relative_size,
+			transform_x: self.transform_x,
+			transform_y: self.transform_y,
+			..Self::stretch(self.pixel_size)
+		}
+	}
+
+	/// Set the contrast enhancement from the window options and the statistics of the image.
+	pub fn set_contrast(mut self, options: &WindowOptions, statistics: Option<&ImageStatistics>) -> Self {
+		let statistics = match statistics {
+			Some(x) => x,
+			None => {
+				self.contrast_mode = 0;
+				return self;
+			},
+		};
+
+		let (mode, (low, high)) = match options.contrast_mode {
+			ContrastMode::Normal => (0, (0.0, 1.0)),
+			ContrastMode::MinMax => (1, statistics.min_max()),
+			ContrastMode::Percentile => (1, statistics.percentile_range(options.contrast_percentile)),
+			ContrastMode::Equalize => (2, statistics.min_max()),
+		};
+		self.contrast_mode = mode;
+		self.contrast_low = low;
+		self.contrast_high = high;
+		self
+	}
+}
+
+/// Convert a color to a vector of 32-bit floats, as used in the uniforms.
+fn color_to_vec4(color: &Color) -> [f32; 4] {
+	[color.red as f32, color.green as f32, color.blue as f32, color.alpha as f32]
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use assert2::assert;
+
+	#[test]
+	fn window_uniforms_size() {
+		// The std140 size of the uniform block in the shaders is rounded up to the alignment of its largest member.
+		// Wgpu rejects the pipeline if the binding is smaller than that.
+		assert!(std::mem::size_of::<WindowUniforms>() == 160);
+	}
+
+	/// Check if two positions are equal up to rounding errors.
+	fn approx_eq(a: [f64; 2], b: [f64; 2]) -> bool {
+		(a[0] - b[0]).abs() < 1e-3 && (a[1] - b[1]).abs() < 1e-3
+	}
+
+	/// Get the uniforms for an image that is fitted in a window, like [`Window::calculate_uniforms`].
+	fn fit_uniforms(window_size: [f32; 2], image_size: [f32; 2], view_transform: &ViewTransform) -> WindowUniforms {
+		let uniforms = WindowUniforms {
+			pixel_size: image_size,
+			..WindowUniforms::fit(window_size, view_transform.bounding_size(image_size))
+		};
+		uniforms.set_view_transform(view_transform)
+	}
+
+	#[test]
+	fn view_transform_sin_cos() {
+		assert!(ViewTransform::new(0.0, false, false).sin_cos() == (0.0, 1.0));
+		assert!(ViewTransform::new(90.0, false, false).sin_cos() == (1.0, 0.0));
+		assert!(ViewTransform::new(180.0, false, false).sin_cos() == (0.0, -1.0));
+		assert!(ViewTransform::new(270.0, false, false).sin_cos() == (-1.0, 0.0));
+
+		// Rotations outside of [0, 360) are normalized.
+		assert!(ViewTransform::new(-90.0, false, false).sin_cos() == (-1.0, 0.0));
+		assert!(ViewTransform::new(450.0, false, false).sin_cos() == (1.0, 0.0));
+		assert!(ViewTransform::default().rotate(-90.0).rotation == 270.0);
+
+		let (sin, cos) = ViewTransform::new(30.0, false, false).sin_cos();
+		assert!((sin - 0.5).abs() < 1e-6);
+		assert!((cos - 0.75f32.sqrt()).abs() < 1e-6);
+	}
+
+	#[test]
+	fn view_transform_bounding_size() {
+		assert!(ViewTransform::new(0.0, false, false).bounding_size([40.0, 20.0]) == [40.0, 20.0]);
+		assert!(ViewTransform::new(90.0, false, false).bounding_size([40.0, 20.0]) == [20.0, 40.0]);
+		assert!(ViewTransform::new(180.0, false, false).bounding_size([40.0, 20.0]) == [40.0, 20.0]);
+		assert!(ViewTransform::new(270.0, true, true).bounding_size([40.0, 20.0]) == [20.0, 40.0]);
+
+		let [width, height] = ViewTransform::new(45.0, false, false).bounding_size([40.0, 20.0]);
+		assert!((width - 60.0 * 0.5f32.sqrt()).abs() < 1e-4);
+		assert!((height - 60.0 * 0.5f32.sqrt()).abs() < 1e-4);
+	}
+
+	#[test]
+	fn view_transform_matrix() {
+		assert!(ViewTransform::new(0.0, false, false).matrix([40.0, 20.0]) == [[1.0, 0.0], [0.0, 1.0]]);
+		assert!(ViewTransform::new(0.0, true, false).matrix([40.0, 20.0]) == [[-1.0, 0.0], [0.0, 1.0]]);
+		assert!(ViewTransform::new(0.0, false, true).matrix([40.0, 20.0]) == [[1.0, 0.0], [0.0, -1.0]]);
+		assert!(ViewTransform::new(180.0, false, false).matrix([40.0, 20.0]) == [[-1.0, 0.0], [0.0, -1.0]]);
+
+		// A clockwise rotation maps the X axis of the image to the negative Y axis (pointing up) of the bounding box.
+		assert!(ViewTransform::new(90.0, false, false).matrix([40.0, 20.0]) == [[0.0, -1.0], [1.0, 0.0]]);
+		assert!(ViewTransform::new(270.0, false, false).matrix([40.0, 20.0]) == [[0.0, 1.0], [-1.0, 0.0]]);
+
+		// Empty images are not transformed.
+		assert!(ViewTransform::new(90.0, false, false).matrix([0.0, 0.0]) == [[1.0, 0.0], [0.0, 1.0]]);
+	}
+
+	#[test]
+	fn window_to_normalized() {
+		// A 40x20 image rotated clockwise fills a 100x200 window.
+		let uniforms = fit_uniforms([100.0, 200.0], [40.0, 20.0], &ViewTransform::new(90.0, false, false));
+
+		// The top left corner of the image is now at the top right of the window.
+		let position = uniforms.window_to_normalized([100.0, 200.0], [100.0, 0.0]).unwrap();
+		assert!(approx_eq(position, [0.0, 1.0]));
+		let position = uniforms.normalized_to_window([100.0, 200.0], [0.0, 1.0]).unwrap();
+		assert!(approx_eq(position, [100.0, 0.0]));
+
+		// The top right corner of the image is now at the bottom right of the window.
+		let position = uniforms.normalized_to_window([100.0, 200.0], [1.0, 1.0]).unwrap();
+		assert!(approx_eq(position, [100.0, 200.0]));
+
+		// Empty windows have no coordinates.
+		assert!(uniforms.window_to_normalized([0.0, 200.0], [0.0, 0.0]) == None);
+		assert!(uniforms.normalized_to_window([100.0, 0.0], [0.0, 0.0]) == None);
+	}
+
+	#[test]
+	fn image_area() {
+		// A square image fitted in a 200x100 window, zoomed out and panned to the right and up.
+		// The area has the origin at the top left, so panning up decreases the Y coordinate.
+		let uniforms = WindowUniforms::fit([200.0, 100.0], [100.0, 100.0]).set_zoom(0.5).set_translation([0.125, 0.25]);
+		assert!(uniforms.image_area([200, 100]) == Rectangle::from_xywh(75, 25, 50, 50));
+
+		// Without panning, the zoomed image stays in the bottom left corner of the fitted area.
+		let uniforms = WindowUniforms::fit([200.0, 100.0], [100.0, 100.0]).set_zoom(0.5);
+		assert!(uniforms.image_area([200, 100]) == Rectangle::from_xywh(50, 50, 50, 50));
+	}
+
+	#[test]
+	fn window_to_normalized_round_trip() {
+		let transforms = [
+			ViewTransform::default(),
+			ViewTransform::new(90.0, false, false),
+			ViewTransform::new(180.0, true, false),
+			ViewTransform::new(270.0, false, true),
+			ViewTransform::new(30.0, true, true),
+		];
+		let window_size = [300.0, 200.0];
+		for view_transform in &transforms {
+			let uniforms = fit_uniforms([300.0, 200.0], [40.0, 20.0], view_transform).set_zoom(1.5).set_translation([0.1, -0.2]);
+			for &position in &[[0.0, 0.0], [150.0, 100.0], [12.5, 180.0], [-20.0, 250.0]] {
+				let normalized = uniforms.window_to_normalized(window_size, position).unwrap();
+				let window = uniforms.normalized_to_window(window_size, normalized).unwrap();
+				assert!(approx_eq(window, position), "view_transform: {:?}, position: {:?}", view_transform, position);
+			}
+		}
+	}
 }
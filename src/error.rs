@@ -15,6 +15,15 @@ pub enum ImageDataError {
 	/// The image data is not in a supported format.
 	UnsupportedImageFormat(UnsupportedImageFormat),
 
+	/// The image has a width or height of zero.
+	ZeroSize(ZeroSize),
+
+	/// The strides of the image cause samples to overlap.
+	OverlappingStrides(OverlappingStrides),
+
+	/// The data buffer is too small for the image.
+	BufferTooSmall(BufferTooSmall),
+
 	/// An other error occured.
 	Other(String),
 }
@@ -26,6 +35,39 @@ pub struct UnsupportedImageFormat {
 	pub format: String,
 }
 
+/// An error indicating that the image has a width or height of zero.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ZeroSize {
+	/// The width of the image.
+	pub width: u32,
+
+	/// The height of the image.
+	pub height: u32,
+}
+
+/// An error indicating that the strides of an image cause samples to overlap.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct OverlappingStrides {
+	/// The name of the stride that is too small.
+	pub stride: &'static str,
+
+	/// The value of the stride in bytes.
+	pub value: u32,
+
+	/// The minimum value of the stride in bytes.
+	pub minimum: u64,
+}
+
+/// An error indicating that the data buffer is too small for the image.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct BufferTooSmall {
+	/// The required size of the buffer in bytes.
+	pub required: u64,
+
+	/// The actual size of the buffer in bytes.
+	pub actual: u64,
+}
+
 /// The window ID is not valid.
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct InvalidWindowId {
@@ -92,6 +134,24 @@ impl From<UnsupportedImageFormat> for ImageDataError {
 	}
 }
 
+impl From<ZeroSize> for ImageDataError {
+	fn from(other: ZeroSize) -> Self {
+		Self::ZeroSize(other)
+	}
+}
+
+impl From<OverlappingStrides> for ImageDataError {
+	fn from(other: OverlappingStrides) -> Self {
+		Self::OverlappingStrides(other)
+	}
+}
+
+impl From<BufferTooSmall> for ImageDataError {
+	fn from(other: BufferTooSmall) -> Self {
+		Self::BufferTooSmall(other)
+	}
+}
+
 impl From<String> for ImageDataError {
 	fn from(other: String) -> Self {
 		Self::Other(other)
@@ -135,6 +195,9 @@ impl From<png::EncodingError> for SaveImageError {
 impl std::error::Error for CreateWindowError {}
 impl std::error::Error for ImageDataError {}
 impl std::error::Error for UnsupportedImageFormat {}
+impl std::error::Error for ZeroSize {}
+impl std::error::Error for OverlappingStrides {}
+impl std::error::Error for BufferTooSmall {}
 impl std::error::Error for InvalidWindowId {}
 impl std::error::Error for SetImageError {}
 impl std::error::Error for GetDeviceError {}
@@ -153,6 +216,9 @@ impl std::fmt::Display for ImageDataError {
 	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
 		match self {
 			Self::UnsupportedImageFormat(e) => write!(f, "{}", e),
+			Self::ZeroSize(e) => write!(f, "{}", e),
+			Self::OverlappingStrides(e) => write!(f, "{}", e),
+			Self::BufferTooSmall(e) => write!(f, "{}", e),
 			Self::Other(e) => write!(f, "{}", e),
 		}
 	}
@@ -164,6 +230,32 @@ impl std::fmt::Display for UnsupportedImageFormat {
 	}
 }
 
+impl std::fmt::Display for ZeroSize {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		write!(f, "image has zero size: {}x{}", self.width, self.height)
+	}
+}
+
+impl std::fmt::Display for OverlappingStrides {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		write!(
+			f,
+			"{} of {} bytes causes samples to overlap, it must be at least {} bytes",
+			self.stride, self.value, self.minimum
+		)
+	}
+}
+
+impl std::fmt::Display for BufferTooSmall {
+	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+		write!(
+			f,
+			"image data is too small: need {} bytes, but got only {} bytes",
+			self.required, self.actual
+		)
+	}
+}
+
 impl std::fmt::Display for InvalidWindowId {
 	fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
 		write!(f, "invalid window ID: {:?}", self.window_id)
@@ -264,6 +264,9 @@ pub struct WindowMouseMoveEvent {
 	/// The new position of the cursor in physical pixels, relative to the top-left corner of the window.
 	pub position: PhysicalPosition<f64>,
 
+	/// The cursor position in [image coordinates](crate::WindowHandle::window_to_image), if the window has an image.
+	pub image_position: Option<[f64; 2]>,
+
 	/// The pressed state of all mouse buttons.
 	pub buttons: MouseButtonState,
 
@@ -289,6 +292,9 @@ pub struct WindowMouseButtonEvent {
 	/// The current position of the mouse cursor inside the window.
 	pub position: PhysicalPosition<f64>,
 
+	/// The cursor position in [image coordinates](crate::WindowHandle::window_to_image), if it is known and the window has an image.
+	pub image_position: Option<[f64; 2]>,
+
 	/// The position of the mouse cursor before it was moved.
 	pub previous_position: Option<PhysicalPosition<f64>>,
 
@@ -317,6 +323,9 @@ pub struct WindowMouseWheelEvent {
 	/// The current position of the mouse cursor inside the window.
 	pub position: Option<PhysicalPosition<f64>>,
 
+	/// The cursor position in [image coordinates](crate::WindowHandle::window_to_image), if it is known and the window has an image.
+	pub image_position: Option<[f64; 2]>,
+
 	/// The position of the mouse cursor before it was moved.
 	pub previous_position: Option<PhysicalPosition<f64>>,
 
@@ -8,6 +8,7 @@ use crate::Image;
 use crate::ImageInfo;
 use crate::ImageView;
 use crate::PixelFormat;
+use crate::ValueRange;
 
 impl AsImageView for image::DynamicImage {
 	fn as_image_view(&self) -> Result<ImageView, ImageDataError> {
@@ -68,21 +69,62 @@ where
 	}
 }
 
+impl<P> AsImageView for image::ImageBuffer<P, Vec<u16>>
+where
+	P: image::Pixel<Subpixel = u16> + 'static,
+{
+	fn as_image_view(&self) -> Result<ImageView<'_>, ImageDataError> {
+		let info = info(self)?;
+		let data = as_bytes_u16(self);
+		Ok(ImageView::new(info, data))
+	}
+}
+
+impl<P> AsImageView for &'_ image::ImageBuffer<P, Vec<u16>>
+where
+	P: image::Pixel<Subpixel = u16> + 'static,
+{
+	fn as_image_view(&self) -> Result<ImageView<'_>, ImageDataError> {
+		(*self).as_image_view()
+	}
+}
+
+impl<P> From<image::ImageBuffer<P, Vec<u16>>> for Image
+where
+	P: image::Pixel<Subpixel = u16> + 'static,
+{
+	fn from(other: image::ImageBuffer<P, Vec<u16>>) -> Self {
+		let info = match info(&other) {
+			Ok(x) => x,
+			Err(e) => return Self::Invalid(e),
+		};
+		let data = into_bytes_u16(other);
+		BoxImage::new(info, data).into()
+	}
+}
+
 /// Consume an [`image::ImageBuffer`] and return the pixel data as boxed slice.
 fn into_bytes<P: 'static + image::Pixel<Subpixel = u8>>(buffer: image::ImageBuffer<P, Vec<u8>>) -> Box<[u8]> {
 	buffer.into_raw().into_boxed_slice()
 }
 
+/// Consume an [`image::ImageBuffer`] with 16-bit samples and return the pixel data as boxed byte slice.
+///
+/// The data has to be copied, since a `Box<[u8]>` can not take ownership of memory allocated for `u16` values.
+fn into_bytes_u16<P: 'static + image::Pixel<Subpixel = u16>>(buffer: image::ImageBuffer<P, Vec<u16>>) -> Box<[u8]> {
+	as_bytes_u16(&buffer).into()
+}
+
 fn dynamic_image_into_bytes(image: image::DynamicImage) -> Box<[u8]> {
 	match image {
 		image::DynamicImage::ImageLuma8(x) => into_bytes(x),
 		image::DynamicImage::ImageLumaA8(x) => into_bytes(x),
-		image::DynamicImage::ImageLuma16(_) => panic!("unsupported pixel format: Luma16"),
-		image::DynamicImage::ImageLumaA16(_) => panic!("unsupported pixel format: LumaA16"),
+		image::DynamicImage::ImageLuma16(x) => into_bytes_u16(x),
+		image::DynamicImage::ImageLumaA16(x) => into_bytes_u16(x),
 		image::DynamicImage::ImageRgb8(x) => into_bytes(x),
 		image::DynamicImage::ImageRgba8(x) => into_bytes(x),
-		image::DynamicImage::ImageRgb16(_) => panic!("unsupported pixel format: Rgb16"),
-		image::DynamicImage::ImageRgba16(_) => panic!("unsupported pixel format: Rgba16"),
+		image::DynamicImage::ImageRgb16(x) => into_bytes_u16(x),
+		image::DynamicImage::ImageRgba16(x) => into_bytes_u16(x),
 		image::DynamicImage::ImageBgr8(x) => into_bytes(x),
 		image::DynamicImage::ImageBgra8(x) => into_bytes(x),
 	}
@@ -93,16 +135,22 @@ fn as_bytes<P: 'static + image::Pixel<Subpixel = u8>>(buffer: &image::ImageBuffe
 	&*buffer
 }
 
+/// Get the pixel data of an [`image::ImageBuffer`] with 16-bit samples as a byte slice.
+fn as_bytes_u16<P: 'static + image::Pixel<Subpixel = u16>>(buffer: &image::ImageBuffer<P, Vec<u16>>) -> &[u8] {
+	let data: &[u16] = buffer;
+	unsafe { std::slice::from_raw_parts(data.as_ptr() as *const u8, data.len() * 2) }
+}
+
 fn dynamic_image_as_bytes(image: &image::DynamicImage) -> &[u8] {
 	match image {
 		image::DynamicImage::ImageLuma8(x) => as_bytes(x),
 		image::DynamicImage::ImageLumaA8(x) => as_bytes(x),
-		image::DynamicImage::ImageLuma16(_) => panic!("unsupported pixel format: Luma16"),
-		image::DynamicImage::ImageLumaA16(_) => panic!("unsupported pixel format: LumaA16"),
+		image::DynamicImage::ImageLuma16(x) => as_bytes_u16(x),
+		image::DynamicImage::ImageLumaA16(x) => as_bytes_u16(x),
 		image::DynamicImage::ImageRgb8(x) => as_bytes(x),
 		image::DynamicImage::ImageRgba8(x) => as_bytes(x),
-		image::DynamicImage::ImageRgb16(_) => panic!("unsupported pixel format: Rgb16"),
-		image::DynamicImage::ImageRgba16(_) => panic!("unsupported pixel format: Rgba16"),
+		image::DynamicImage::ImageRgb16(x) => as_bytes_u16(x),
+		image::DynamicImage::ImageRgba16(x) => as_bytes_u16(x),
 		image::DynamicImage::ImageBgr8(x) => as_bytes(x),
 		image::DynamicImage::ImageBgra8(x) => as_bytes(x),
 	}
@@ -111,15 +159,22 @@ fn dynamic_image_as_bytes(image: &image::DynamicImage) -> &[u8] {
 /// Extract the [`ImageInfo`] from an [`image::ImageBuffer`].
 fn info<P, C>(image: &image::ImageBuffer<P, C>) -> Result<ImageInfo, ImageDataError>
 where
-	P: image::Pixel<Subpixel = u8> + 'static,
-	C: std::ops::Deref<Target = [u8]>,
+	P: image::Pixel + 'static,
+	C: std::ops::Deref<Target = [P::Subpixel]>,
 {
+	// The sample layout uses strides in samples, not bytes.
+	let sample_size = std::mem::size_of::<P::Subpixel>();
 	Ok(ImageInfo {
 		pixel_format: pixel_format::<P>()?,
 		width: image.width(),
 		height: image.height(),
-		stride_x: image.sample_layout().width_stride as u32,
-		stride_y: image.sample_layout().height_stride as u32,
+		stride_x: (image.sample_layout().width_stride * sample_size) as u32,
+		stride_y: (image.sample_layout().height_stride * sample_size) as u32,
+		stride_c: (image.sample_layout().channel_stride * sample_size) as u32,
+		value_range: ValueRange::Full,
+		chroma: Default::default(),
+		flip_x: false,
+		flip_y: false,
 	})
 }
 
@@ -127,12 +182,12 @@ fn dynamic_image_info(image: &image::DynamicImage) -> Result<ImageInfo, ImageDat
 	match image {
 		image::DynamicImage::ImageLuma8(x) => info(x),
 		image::DynamicImage::ImageLumaA8(x) => info(x),
-		image::DynamicImage::ImageLuma16(_) => Err("unsupported pixel format: Luma16".into()),
-		image::DynamicImage::ImageLumaA16(_) => Err("unsupported pixel format: LumaA16".into()),
+		image::DynamicImage::ImageLuma16(x) => info(x),
+		image::DynamicImage::ImageLumaA16(x) => info(x),
 		image::DynamicImage::ImageRgb8(x) => info(x),
 		image::DynamicImage::ImageRgba8(x) => info(x),
-		image::DynamicImage::ImageRgb16(_) => Err("unsupported pixel format: Rgb16".into()),
-		image::DynamicImage::ImageRgba16(_) => Err("unsupported pixel format: Rgba16".into()),
+		image::DynamicImage::ImageRgb16(x) => info(x),
+		image::DynamicImage::ImageRgba16(x) => info(x),
 		image::DynamicImage::ImageBgr8(x) => info(x),
 		image::DynamicImage::ImageBgra8(x) => info(x),
 	}
@@ -147,6 +202,10 @@ fn pixel_format<P: image::Pixel>() -> Result<PixelFormat, ImageDataError> {
 		image::ColorType::Bgra8 => Ok(PixelFormat::Bgra8(Alpha::Unpremultiplied)),
 		image::ColorType::Rgb8 => Ok(PixelFormat::Rgb8),
 		image::ColorType::Rgba8 => Ok(PixelFormat::Rgba8(Alpha::Unpremultiplied)),
+		image::ColorType::L16 => Ok(PixelFormat::Mono16),
+		image::ColorType::La16 => Ok(PixelFormat::MonoAlpha16(Alpha::Unpremultiplied)),
+		image::ColorType::Rgb16 => Ok(PixelFormat::Rgb16),
+		image::ColorType::Rgba16 => Ok(PixelFormat::Rgba16(Alpha::Unpremultiplied)),
 		x => Err(format!("unsupported color type: {:?}", x).into()),
 	}
 }
@@ -30,17 +30,18 @@
 //! ```
 
 use crate::error::ImageDataError;
+use crate::image_info::SampleType;
 use crate::Alpha;
 use crate::BoxImage;
 use crate::Image;
 use crate::ImageInfo;
 use crate::PixelFormat;
+use crate::ValueRange;
 
 /// Wrapper for [`tch::Tensor`] that implements `Into<Image>`.
 pub struct TensorImage<'a> {
 	tensor: &'a tch::Tensor,
 	info: ImageInfo,
-	planar: bool,
 }
 
 /// The pixel format of a tensor, or a color format to guess the pixel format.
@@ -69,8 +70,12 @@ pub enum ColorFormat {
 /// Extension trait to allow displaying tensors as image.
 ///
 /// The tensor data will always be copied.
-/// Additionally, the data will be converted to 8 bit integers,
-/// and planar data will be converted to interlaced data.
+/// Additionally, the data will be converted to the sample type of the pixel format.
+/// Planar data is displayed directly, without converting it to interlaced data.
+///
+/// Only pixel formats with 8-bit integer or 32-bit floating point samples are supported for tensors.
+/// When guessing the pixel format, floating point tensors with 1, 3 or 4 channels interpreted as RGB are displayed with 32-bit floating point pixel formats.
+/// All other tensors are converted to 8-bit integers.
 ///
 /// The original tensor is unaffected, but the conversion can be expensive.
 /// If you also need to convert the tensor, consider doing so before displaying it.
@@ -166,27 +171,31 @@ pub trait TensorAsImage {
 
 impl TensorAsImage for tch::Tensor {
 	fn as_image(&self, pixel_format: TensorPixelFormat) -> Result<TensorImage, ImageDataError> {
-		let (planar, info) = match pixel_format {
+		let info = match pixel_format {
 			TensorPixelFormat::Planar(pixel_format) => tensor_info(self, pixel_format, true)?,
 			TensorPixelFormat::Interlaced(pixel_format) => tensor_info(self, pixel_format, false)?,
 			TensorPixelFormat::Guess(color_format) => guess_tensor_info(self, color_format)?,
 		};
-		Ok(TensorImage {
-			tensor: self,
-			info,
-			planar,
-		})
+		Ok(TensorImage { tensor: self, info })
 	}
 }
 
 impl<'a> From<TensorImage<'a>> for Image {
 	fn from(other: TensorImage<'a>) -> Self {
-		let data: Vec<u8> = match other.planar {
-			true => other.tensor.permute(&[1, 2, 0]).into(),
-			false => other.tensor.into(),
+		let data = match other.info.pixel_format.sample_type() {
+			SampleType::U8 => Vec::<u8>::from(other.tensor).into_boxed_slice(),
+			SampleType::F32 => {
+				let values = Vec::<f32>::from(other.tensor);
+				let mut data = Vec::with_capacity(values.len() * 4);
+				for value in values {
+					data.extend_from_slice(&value.to_ne_bytes());
+				}
+				data.into_boxed_slice()
+			},
+			x => return Image::Invalid(format!("unsupported sample type for tensors: {:?}", x).into()),
 		};
 
-		BoxImage::new(other.info, data.into_boxed_slice()).into()
+		BoxImage::new(other.info, data).into()
 	}
 }
 
@@ -200,7 +209,12 @@ impl<'a> From<Result<TensorImage<'a>, ImageDataError>> for Image {
 }
 
 /// Compute the image info of a tensor, given a known pixel format.
-fn tensor_info(tensor: &tch::Tensor, pixel_format: PixelFormat, planar: bool) -> Result<(bool, ImageInfo), String> {
+fn tensor_info(tensor: &tch::Tensor, pixel_format: PixelFormat, planar: bool) -> Result<ImageInfo, String> {
+	match pixel_format.sample_type() {
+		SampleType::U8 | SampleType::F32 if pixel_format.yuv_encoding().is_none() && pixel_format != PixelFormat::Indexed8 => (),
+		_ => return Err(format!("unsupported pixel format for tensors: {:?}", pixel_format)),
+	}
+
 	let expected_channels = pixel_format.channels();
 	let dimensions = tensor.dim();
 
@@ -211,19 +225,19 @@ fn tensor_info(tensor: &tch::Tensor, pixel_format: PixelFormat, planar: bool) ->
 			if channels != i64::from(expected_channels) {
 				Err(format!("expected shape ({}, height, width), found {:?}", expected_channels, shape))
 			} else {
-				Ok((false, ImageInfo::new(pixel_format, width as u32, height as u32)))
+				Ok(ImageInfo::planar(pixel_format, width as u32, height as u32))
 			}
 		} else {
 			let (height, width, channels) = shape;
 			if channels != i64::from(expected_channels) {
 				Err(format!("expected shape (height, width, {}), found {:?}", expected_channels, shape))
 			} else {
-				Ok((false, ImageInfo::new(pixel_format, width as u32, height as u32)))
+				Ok(ImageInfo::new(pixel_format, width as u32, height as u32))
 			}
 		}
 	} else if dimensions == 2 && expected_channels == 1 {
 		let (height, width) = tensor.size2().unwrap();
-		Ok((false, ImageInfo::new(pixel_format, width as u32, height as u32)))
+		Ok(ImageInfo::new(pixel_format, width as u32, height as u32))
 	} else {
 		Err(format!(
 			"wrong number of dimensions ({}) for format ({:?})",
@@ -233,32 +247,46 @@ fn tensor_info(tensor: &tch::Tensor, pixel_format: PixelFormat, planar: bool) ->
 }
 
 /// Guess the image info of a tensor.
-fn guess_tensor_info(tensor: &tch::Tensor, color_format: ColorFormat) -> Result<(bool, ImageInfo), String> {
+fn guess_tensor_info(tensor: &tch::Tensor, color_format: ColorFormat) -> Result<ImageInfo, String> {
 	let dimensions = tensor.dim();
+	let float = matches!(tensor.kind(), tch::Kind::Half | tch::Kind::Float | tch::Kind::Double);
 
-	if dimensions == 2 {
+	let mono = if float { PixelFormat::Mono32F } else { PixelFormat::Mono8 };
+	let rgb = if float { PixelFormat::Rgb32F } else { PixelFormat::Rgb8 };
+	let rgba = if float { PixelFormat::Rgba32F(Alpha::Unpremultiplied) } else { PixelFormat::Rgba8(Alpha::Unpremultiplied) };
+	let bgr = PixelFormat::Bgr8;
+	let bgra = PixelFormat::Bgra8(Alpha::Unpremultiplied);
+
+	let info = if dimensions == 2 {
 		let (height, width) = tensor.size2().unwrap();
-		Ok((false, ImageInfo::mono8(width as u32, height as u32)))
+		ImageInfo::new(mono, width as u32, height as u32)
 	} else if dimensions == 3 {
 		let shape = tensor.size3().unwrap();
 		match (shape.0 as u32, shape.1 as u32, shape.2 as u32, color_format) {
-			(h, w, 1, _) => Ok((false, ImageInfo::mono8(w, h))),
-			(1, h, w, _) => Ok((false, ImageInfo::mono8(w, h))), // "planar" doesn't do anything here, so call it interlaced
-			(h, w, 3, ColorFormat::Rgb) => Ok((false, ImageInfo::rgb8(w, h))),
-			(h, w, 3, ColorFormat::Bgr) => Ok((false, ImageInfo::bgr8(w, h))),
-			(3, h, w, ColorFormat::Rgb) => Ok((true, ImageInfo::rgb8(w, h))),
-			(3, h, w, ColorFormat::Bgr) => Ok((true, ImageInfo::bgr8(w, h))),
-			(h, w, 4, ColorFormat::Rgb) => Ok((false, ImageInfo::rgba8(w, h))),
-			(h, w, 4, ColorFormat::Bgr) => Ok((false, ImageInfo::bgra8(w, h))),
-			(4, h, w, ColorFormat::Rgb) => Ok((true, ImageInfo::rgba8(w, h))),
-			(4, h, w, ColorFormat::Bgr) => Ok((true, ImageInfo::bgra8(w, h))),
-			_ => Err(format!("unable to guess pixel format for tensor with shape {:?}, expected (height, width) or (height, width, channels) or (channels, height, width) where channels is either 1, 3 or 4", shape))
+			(h, w, 1, _) => ImageInfo::new(mono, w, h),
+			(1, h, w, _) => ImageInfo::new(mono, w, h), // "planar" doesn't do anything here, so call it interlaced
+			(h, w, 3, ColorFormat::Rgb) => ImageInfo::new(rgb, w, h),
+			(h, w, 3, ColorFormat::Bgr) => ImageInfo::new(bgr, w, h),
+			(3, h, w, ColorFormat::Rgb) => ImageInfo::planar(rgb, w, h),
+			(3, h, w, ColorFormat::Bgr) => ImageInfo::planar(bgr, w, h),
+			(h, w, 4, ColorFormat::Rgb) => ImageInfo::new(rgba, w, h),
+			(h, w, 4, ColorFormat::Bgr) => ImageInfo::new(bgra, w, h),
+			(4, h, w, ColorFormat::Rgb) => ImageInfo::planar(rgba, w, h),
+			(4, h, w, ColorFormat::Bgr) => ImageInfo::planar(bgra, w, h),
+			_ => return Err(format!("unable to guess pixel format for tensor with shape {:?}, expected (height, width) or (height, width, channels) or (channels, height, width) where channels is either 1, 3 or 4", shape))
 		}
 	} else {
-		Err(format!(
+		return Err(format!(
 			"unable to guess pixel format for tensor with {} dimensions, expected 2 or 3 dimensions",
 			dimensions
-		))
+		));
+	};
+
+	// Float tensors do not always hold values between 0 and 1, so map the values that are present to the full display range.
+	if info.pixel_format.sample_type() == SampleType::F32 {
+		Ok(ImageInfo { value_range: ValueRange::Auto, ..info })
+	} else {
+		Ok(info)
 	}
 }
 
@@ -283,10 +311,10 @@ mod test {
 		assert!(data.reshape(&[5, 6, 4]).as_image_guess_bgr().map(|x| x.info) == Ok(ImageInfo::bgra8(6, 5)));
 
 		// Guess RGB[A]/BGR[A] from planar data.
-		assert!(data.reshape(&[3, 8, 5]).as_image_guess_rgb().map(|x| x.info) == Ok(ImageInfo::rgb8(5, 8)));
-		assert!(data.reshape(&[3, 8, 5]).as_image_guess_bgr().map(|x| x.info) == Ok(ImageInfo::bgr8(5, 8)));
-		assert!(data.reshape(&[4, 5, 6]).as_image_guess_rgb().map(|x| x.info) == Ok(ImageInfo::rgba8(6, 5)));
-		assert!(data.reshape(&[4, 5, 6]).as_image_guess_bgr().map(|x| x.info) == Ok(ImageInfo::bgra8(6, 5)));
+		assert!(data.reshape(&[3, 8, 5]).as_image_guess_rgb().map(|x| x.info) == Ok(ImageInfo::planar(PixelFormat::Rgb8, 5, 8)));
+		assert!(data.reshape(&[3, 8, 5]).as_image_guess_bgr().map(|x| x.info) == Ok(ImageInfo::planar(PixelFormat::Bgr8, 5, 8)));
+		assert!(data.reshape(&[4, 5, 6]).as_image_guess_rgb().map(|x| x.info) == Ok(ImageInfo::planar(PixelFormat::Rgba8(Alpha::Unpremultiplied), 6, 5)));
+		assert!(data.reshape(&[4, 5, 6]).as_image_guess_bgr().map(|x| x.info) == Ok(ImageInfo::planar(PixelFormat::Bgra8(Alpha::Unpremultiplied), 6, 5)));
 
 		// Fail to guess on other dimensions
 		assert!(let Err(_) = data.reshape(&[120]).as_image_guess_rgb().map(|x| x.info));
@@ -294,6 +322,18 @@ mod test {
 		assert!(let Err(_) = data.reshape(&[6, 10, 2]).as_image_guess_rgb().map(|x| x.info));
 		assert!(let Err(_) = data.reshape(&[8, 5, 3, 1]).as_image_guess_rgb().map(|x| x.info));
 		assert!(let Err(_) = data.reshape(&[4, 5, 6, 1]).as_image_guess_rgb().map(|x| x.info));
+
+		// Guess floating point formats from floating point data.
+		let data = tch::Tensor::of_slice(&(0..120).map(|x| x as f32).collect::<Vec<f32>>());
+		let auto = |info: ImageInfo| ImageInfo { value_range: ValueRange::Auto, ..info };
+		assert!(data.reshape(&[12, 10]).as_image_guess_rgb().map(|x| x.info) == Ok(auto(ImageInfo::mono32f(10, 12))));
+		assert!(data.reshape(&[8, 5, 3]).as_image_guess_rgb().map(|x| x.info) == Ok(auto(ImageInfo::rgb32f(5, 8))));
+		assert!(data.reshape(&[4, 5, 6]).as_image_guess_rgb().map(|x| x.info) == Ok(auto(ImageInfo::planar(PixelFormat::Rgba32F(Alpha::Unpremultiplied), 6, 5))));
+		assert!(data.reshape(&[8, 5, 3]).as_image_guess_bgr().map(|x| x.info) == Ok(ImageInfo::bgr8(5, 8)));
+
+		// Guessed float images use the value range of the data, converted 8-bit images use the full range.
+		assert!(data.reshape(&[12, 10]).as_image_guess_rgb().map(|x| x.info.value_range) == Ok(ValueRange::Auto));
+		assert!(data.reshape(&[8, 5, 3]).as_image_guess_bgr().map(|x| x.info.value_range) == Ok(ValueRange::Full));
 	}
 
 	#[test]
@@ -308,6 +348,8 @@ mod test {
 		assert!(let Err(_) = data.reshape(&[3, 5, 4]).as_mono8().map(|x| x.info));
 		assert!(let Err(_) = data.reshape(&[4, 5, 3]).as_mono8().map(|x| x.info));
 		assert!(let Err(_) = data.reshape(&[60]).as_mono8().map(|x| x.info));
+		assert!(let Err(_) = data.reshape(&[12, 5]).as_interlaced(PixelFormat::Mono16).map(|x| x.info));
+		assert!(data.reshape(&[12, 5]).as_interlaced(PixelFormat::Mono32F).map(|x| x.info) == Ok(ImageInfo::mono32f(5, 12)));
 
 		// RGB/BGR
 		assert!(data.reshape(&[4, 5, 3]).as_interlaced_rgb8().map(|x| x.info) == Ok(ImageInfo::rgb8(5, 4)));
@@ -335,8 +377,8 @@ mod test {
 		let data = tch::Tensor::of_slice(&(0..60).collect::<Vec<u8>>());
 
 		// RGB/BGR
-		assert!(data.reshape(&[3, 4, 5]).as_planar_rgb8().map(|x| x.info) == Ok(ImageInfo::rgb8(5, 4)));
-		assert!(data.reshape(&[3, 4, 5]).as_planar_bgr8().map(|x| x.info) == Ok(ImageInfo::bgr8(5, 4)));
+		assert!(data.reshape(&[3, 4, 5]).as_planar_rgb8().map(|x| x.info) == Ok(ImageInfo::planar(PixelFormat::Rgb8, 5, 4)));
+		assert!(data.reshape(&[3, 4, 5]).as_planar_bgr8().map(|x| x.info) == Ok(ImageInfo::planar(PixelFormat::Bgr8, 5, 4)));
 		assert!(let Err(_) = data.reshape(&[4, 5, 3, 1]).as_planar_bgr8().map(|x| x.info));
 		assert!(let Err(_) = data.reshape(&[4, 5, 3, 1]).as_planar_bgr8().map(|x| x.info));
 		assert!(let Err(_) = data.reshape(&[4, 5, 3]).as_planar_bgr8().map(|x| x.info));
@@ -345,8 +387,8 @@ mod test {
 		assert!(let Err(_) = data.reshape(&[15, 4]).as_planar_rgb8().map(|x| x.info));
 
 		// RGBA/BGRA
-		assert!(data.reshape(&[4, 3, 5]).as_planar_rgba8().map(|x| x.info) == Ok(ImageInfo::rgba8(5, 3)));
-		assert!(data.reshape(&[4, 3, 5]).as_planar_bgra8().map(|x| x.info) == Ok(ImageInfo::bgra8(5, 3)));
+		assert!(data.reshape(&[4, 3, 5]).as_planar_rgba8().map(|x| x.info) == Ok(ImageInfo::planar(PixelFormat::Rgba8(Alpha::Unpremultiplied), 5, 3)));
+		assert!(data.reshape(&[4, 3, 5]).as_planar_bgra8().map(|x| x.info) == Ok(ImageInfo::planar(PixelFormat::Bgra8(Alpha::Unpremultiplied), 5, 3)));
 		assert!(let Err(_) = data.reshape(&[3, 5, 4, 1]).as_planar_rgba8().map(|x| x.info));
 		assert!(let Err(_) = data.reshape(&[3, 5, 4, 1]).as_planar_bgra8().map(|x| x.info));
 		assert!(let Err(_) = data.reshape(&[3, 5, 4]).as_planar_rgba8().map(|x| x.info));
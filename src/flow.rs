@@ -0,0 +1,68 @@
+use crate::BoxImage;
+use crate::ImageInfo;
+
+/// Create a color-wheel legend for optical flow images.
+///
+/// The legend uses the same color encoding that windows use to show [`PixelFormat::Flow32F`][crate::PixelFormat::Flow32F] images.
+/// The center of the wheel represents no motion,
+/// and the edge of the wheel represents motion with a magnitude equal to the upper bound of the value range of the flow image.
+///
+/// The returned image is an RGBA image of `size` by `size` pixels.
+/// Pixels outside of the wheel are fully transparent.
+pub fn flow_color_wheel(size: u32) -> BoxImage {
+	let radius = size as f32 / 2.0;
+	let mut data = Vec::with_capacity(size as usize * size as usize * 4);
+	for y in 0..size {
+		for x in 0..size {
+			let u = (x as f32 + 0.5 - radius) / radius;
+			let v = (y as f32 + 0.5 - radius) / radius;
+			if u.hypot(v) > 1.0 {
+				data.extend_from_slice(&[0, 0, 0, 0]);
+			} else {
+				let [r, g, b] = flow_color(u, v, 1.0);
+				data.extend_from_slice(&[r, g, b, 255]);
+			}
+		}
+	}
+	BoxImage::new(ImageInfo::rgba8(size, size), data.into_boxed_slice())
+}
+
+/// Get the color of an optical flow vector using the HSV color-wheel encoding.
+///
+/// This must match the encoding used by the fragment shader.
+fn flow_color(u: f32, v: f32, max_magnitude: f32) -> [u8; 3] {
+	let hue = (v.atan2(u) / (2.0 * std::f32::consts::PI)).rem_euclid(1.0);
+	let saturation = (u.hypot(v) / max_magnitude).clamp(0.0, 1.0);
+
+	let channel = |offset: f32| {
+		let k = (((hue * 6.0 + offset) % 6.0 - 3.0).abs() - 1.0).clamp(0.0, 1.0);
+		let value = 1.0 + saturation * (k - 1.0);
+		(value * 255.0).round() as u8
+	};
+	[channel(0.0), channel(4.0), channel(2.0)]
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use assert2::assert;
+
+	#[test]
+	fn color_encoding() {
+		assert!(flow_color(0.0, 0.0, 1.0) == [255, 255, 255]);
+		assert!(flow_color(1.0, 0.0, 1.0) == [255, 0, 0]);
+		assert!(flow_color(4.0, 0.0, 2.0) == [255, 0, 0]);
+		assert!(flow_color(-1.0, 0.0, 1.0) == [0, 255, 255]);
+		assert!(flow_color(0.5, 0.0, 1.0) == [255, 128, 128]);
+	}
+
+	#[test]
+	fn color_wheel() {
+		let wheel = flow_color_wheel(8);
+		assert!(wheel.info() == ImageInfo::rgba8(8, 8));
+		let pixel = |x: usize, y: usize| &wheel.data()[(y * 8 + x) * 4..][..4];
+		assert!(pixel(0, 0) == [0, 0, 0, 0]);
+		assert!(pixel(7, 4)[3] == 255);
+		assert!(pixel(7, 4)[0] == 255);
+	}
+}
@@ -1,7 +1,9 @@
+use std::ops::Range;
 use std::sync::Arc;
 
 use crate::error::ImageDataError;
 use crate::ImageInfo;
+use crate::Rectangle;
 
 /// Trait for borrowing image data from a struct.
 pub trait AsImageView {
@@ -19,12 +21,57 @@ pub fn image_info(image: &impl AsImageView) -> Result<ImageInfo, ImageDataError>
 pub struct ImageView<'a> {
 	info: ImageInfo,
 	data: &'a [u8],
+	palette: Option<&'a [[u8; 4]]>,
 }
 
 impl<'a> ImageView<'a> {
 	/// Create a new image view from image information and a data slice.
+	///
+	/// This does not check if the image information matches the data.
+	/// Invalid images are rejected when they are passed to a window.
+	/// Use [`Self::try_new`] to check the image when the view is created.
 	pub fn new(info: ImageInfo, data: &'a [u8]) -> Self {
-		Self { info, data }
+		Self { info, data, palette: None }
+	}
+
+	/// Create a new image view from image information and a data slice, checking that they match.
+	///
+	/// See [`ImageInfo::validate`] for the checks that are performed.
+	pub fn try_new(info: ImageInfo, data: &'a [u8]) -> Result<Self, ImageDataError> {
+		info.validate(data.len())?;
+		Ok(Self::new(info, data))
+	}
+
+	/// Check that the image information matches the data.
+	///
+	/// See [`ImageInfo::validate`] for the checks that are performed.
+	pub fn validate(&self) -> Result<(), ImageDataError> {
+		self.info.validate(self.data.len())
+	}
+
+	/// Add a palette to the image view.
+	///
+	/// The palette is used by [`PixelFormat::Indexed8`][crate::PixelFormat::Indexed8] images.
+	/// Each entry is an unpremultiplied RGBA color.
+	/// Only the first 256 entries of the palette are used.
+	///
+	/// This function consumes and returns `self` to allow daisy chaining.
+	pub fn with_palette(mut self, palette: &'a [[u8; 4]]) -> Self {
+		self.palette = Some(palette);
+		self
+	}
+
+	/// Get a view of a region of the image, without copying the data.
+	///
+	/// The region is clipped to the image.
+	/// For subsampled YUV formats, the region is extended to start at a whole chroma sample.
+	pub fn crop(&self, area: Rectangle) -> ImageView<'a> {
+		let (info, range) = crop_data(&self.info, &area, self.data.len());
+		Self {
+			info,
+			data: &self.data[range],
+			palette: self.palette,
+		}
 	}
 
 	/// Get the image information.
@@ -36,6 +83,11 @@ impl<'a> ImageView<'a> {
 	pub fn data(&self) -> &[u8] {
 		self.data
 	}
+
+	/// Get the palette of the image, if it has one.
+	pub fn palette(&self) -> Option<&[[u8; 4]]> {
+		self.palette
+	}
 }
 
 impl<'a> AsImageView for ImageView<'a> {
@@ -72,7 +124,7 @@ impl Clone for Image {
 			Self::Arc(x) => Self::Arc(x.clone()),
 			// We can not clone Box<dyn AsImageView> directly, but we can clone the data or the error.
 			Self::BoxDyn(x) => match x.as_image_view() {
-				Ok(view) => Self::Box(BoxImage::new(view.info, view.data.into())),
+				Ok(view) => Self::Box(BoxImage::from(view)),
 				Err(error) => Self::Invalid(error),
 			},
 			Self::ArcDyn(x) => Self::ArcDyn(x.clone()),
@@ -98,6 +150,7 @@ impl<T: AsImageView> AsImageView for Arc<T> {
 pub struct BoxImage {
 	info: ImageInfo,
 	data: Box<[u8]>,
+	palette: Option<Box<[[u8; 4]]>>,
 }
 
 /// Image backed by an `Arc<[u8]>`.
@@ -105,6 +158,10 @@ pub struct BoxImage {
 pub struct ArcImage {
 	info: ImageInfo,
 	data: Arc<[u8]>,
+	palette: Option<Arc<[[u8; 4]]>>,
+
+	/// The range of the image in the data, used for cropped images.
+	range: Range<usize>,
 }
 
 impl Image {
@@ -129,12 +186,33 @@ impl AsImageView for Image {
 impl BoxImage {
 	/// Create a new image from image information and a boxed slice.
 	pub fn new(info: ImageInfo, data: Box<[u8]>) -> Self {
-		Self { info, data }
+		Self { info, data, palette: None }
+	}
+
+	/// Add a palette to the image.
+	///
+	/// See [`ImageView::with_palette`] for details.
+	///
+	/// This function consumes and returns `self` to allow daisy chaining.
+	pub fn with_palette(mut self, palette: Box<[[u8; 4]]>) -> Self {
+		self.palette = Some(palette);
+		self
 	}
 
 	/// Get a non-owning view of the image data.
-	pub fn as_view(&self) -> ImageView {
-		ImageView::new(self.info, &self.data)
+	pub fn as_view(&self) -> ImageView<'_> {
+		ImageView {
+			info: self.info,
+			data: &self.data,
+			palette: self.palette.as_deref(),
+		}
+	}
+
+	/// Get a view of a region of the image, without copying the data.
+	///
+	/// See [`ImageView::crop`] for details.
+	pub fn crop(&self, area: Rectangle) -> ImageView<'_> {
+		self.as_view().crop(area)
 	}
 
 	/// Get the image information.
@@ -146,6 +224,11 @@ impl BoxImage {
 	pub fn data(&self) -> &[u8] {
 		&self.data
 	}
+
+	/// Get the palette of the image, if it has one.
+	pub fn palette(&self) -> Option<&[[u8; 4]]> {
+		self.palette.as_deref()
+	}
 }
 
 impl AsImageView for BoxImage {
@@ -157,12 +240,44 @@ impl AsImageView for BoxImage {
 impl ArcImage {
 	/// Create a new image from image information and a Arc-wrapped slice.
 	pub fn new(info: ImageInfo, data: Arc<[u8]>) -> Self {
-		Self { info, data }
+		Self {
+			info,
+			range: 0..data.len(),
+			data,
+			palette: None,
+		}
+	}
+
+	/// Add a palette to the image.
+	///
+	/// See [`ImageView::with_palette`] for details.
+	///
+	/// This function consumes and returns `self` to allow daisy chaining.
+	pub fn with_palette(mut self, palette: Arc<[[u8; 4]]>) -> Self {
+		self.palette = Some(palette);
+		self
 	}
 
 	/// Get a non-owning view of the image data.
-	pub fn as_view(&self) -> ImageView {
-		ImageView::new(self.info, &self.data)
+	pub fn as_view(&self) -> ImageView<'_> {
+		ImageView {
+			info: self.info,
+			data: self.data(),
+			palette: self.palette.as_deref(),
+		}
+	}
+
+	/// Get a region of the image, sharing the data with this image.
+	///
+	/// See [`ImageView::crop`] for details.
+	pub fn crop(&self, area: Rectangle) -> ArcImage {
+		let (info, range) = crop_data(&self.info, &area, self.range.len());
+		Self {
+			info,
+			data: self.data.clone(),
+			palette: self.palette.clone(),
+			range: self.range.start + range.start..self.range.start + range.end,
+		}
 	}
 
 	/// Get the image information.
@@ -172,7 +287,12 @@ impl ArcImage {
 
 	/// Get the image data as byte slice.
 	pub fn data(&self) -> &[u8] {
-		&self.data
+		&self.data[self.range.clone()]
+	}
+
+	/// Get the palette of the image, if it has one.
+	pub fn palette(&self) -> Option<&[[u8; 4]]> {
+		self.palette.as_deref()
 	}
 }
 
@@ -182,11 +302,23 @@ impl AsImageView for ArcImage {
 	}
 }
 
+/// Get the image info and data range of a region of an image.
+///
+/// The range only covers the bytes spanned by the region and is clipped to the data.
+fn crop_data(info: &ImageInfo, area: &Rectangle, data_size: usize) -> (ImageInfo, Range<usize>) {
+	let (info, offset) = info.crop(area);
+	let start = offset.min(data_size);
+	let size = info.required_size().unwrap_or(u64::MAX);
+	let end = (start as u64).saturating_add(size).min(data_size as u64) as usize;
+	(info, start..end)
+}
+
 impl From<ImageView<'_>> for BoxImage {
 	fn from(other: ImageView) -> Self {
 		Self {
 			info: other.info,
 			data: other.data.into(),
+			palette: other.palette.map(|x| x.into()),
 		}
 	}
 }
@@ -196,6 +328,7 @@ impl From<&'_ ImageView<'_>> for BoxImage {
 		Self {
 			info: other.info,
 			data: other.data.into(),
+			palette: other.palette.map(|x| x.into()),
 		}
 	}
 }
@@ -204,7 +337,9 @@ impl From<ImageView<'_>> for ArcImage {
 	fn from(other: ImageView) -> Self {
 		Self {
 			info: other.info,
+			range: 0..other.data.len(),
 			data: other.data.into(),
+			palette: other.palette.map(|x| x.into()),
 		}
 	}
 }
@@ -213,7 +348,9 @@ impl From<&'_ ImageView<'_>> for ArcImage {
 	fn from(other: &ImageView) -> Self {
 		Self {
 			info: other.info,
+			range: 0..other.data.len(),
 			data: other.data.into(),
+			palette: other.palette.map(|x| x.into()),
 		}
 	}
 }
@@ -234,7 +371,9 @@ impl From<BoxImage> for ArcImage {
 	fn from(other: BoxImage) -> Self {
 		Self {
 			info: other.info,
+			range: 0..other.data.len(),
 			data: other.data.into(),
+			palette: other.palette.map(|x| x.into()),
 		}
 	}
 }
@@ -280,3 +419,40 @@ where
 		Self::ArcDyn(other)
 	}
 }
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use assert2::assert;
+
+	#[test]
+	fn crop() {
+		let data: Vec<u8> = (0..60).collect();
+		let image = ImageView::new(ImageInfo::mono8(6, 10), &data);
+
+		// The cropped data ends at the last pixel of the region.
+		let cropped = image.crop(Rectangle::from_xywh(1, 2, 3, 4));
+		assert!(cropped.data() == &data[13..34]);
+		assert!(let Ok(()) = cropped.validate());
+
+		// Cropping a cropped image only keeps the data of the new region.
+		let cropped = cropped.crop(Rectangle::from_xywh(1, 1, 2, 2));
+		assert!(cropped.data() == &data[20..28]);
+
+		// Regions outside of the image have no data.
+		let cropped = image.crop(Rectangle::from_xywh(10, 20, 3, 4));
+		assert!(cropped.data().is_empty());
+	}
+
+	#[test]
+	fn crop_arc_image() {
+		let data: Vec<u8> = (0..60).collect();
+		let image = ArcImage::new(ImageInfo::mono8(6, 10), data.clone().into());
+
+		let cropped = image.crop(Rectangle::from_xywh(1, 2, 3, 4));
+		assert!(cropped.data() == &data[13..34]);
+		let cropped = cropped.crop(Rectangle::from_xywh(1, 1, 2, 2));
+		assert!(cropped.data() == &data[20..28]);
+		assert!(let Ok(()) = cropped.as_view().validate());
+	}
+}
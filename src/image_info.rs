@@ -1,4 +1,11 @@
+use crate::error::{BufferTooSmall, ImageDataError, OverlappingStrides, ZeroSize};
+use crate::Rectangle;
+
 /// Information describing the binary data of an image.
+///
+/// The location of each sample is determined by the X, Y and channel strides.
+/// The byte offset of channel `c` of the pixel at `(x, y)` is `x * stride_x + y * stride_y + c * stride_c`.
+/// This allows both interlaced and planar data to be described.
 #[derive(Debug, Clone, Copy, Eq, PartialEq)]
 pub struct ImageInfo {
 	/// The pixel format of the image data.
@@ -15,6 +22,38 @@ pub struct ImageInfo {
 
 	/// The Y stride of the image data in bytes.
 	pub stride_y: u32,
+
+	/// The channel stride of the image data in bytes.
+	///
+	/// For interlaced data this is equal to the size of a single sample.
+	/// For planar data this is the size of a full plane.
+	pub stride_c: u32,
+
+	/// The range of sample values that is mapped to the full display range.
+	///
+	/// This does not affect the alpha channel, which always uses the full range of the sample type.
+	/// It is also ignored for YUV formats, which use the range from their [`YuvEncoding`].
+	pub value_range: ValueRange,
+
+	/// The location of the chroma samples for YUV formats.
+	///
+	/// For YUV formats, the X and Y stride describe the location of the luma samples,
+	/// and the channel stride is not used.
+	/// For all other formats this field is ignored.
+	pub chroma: ChromaLayout,
+
+	/// If true, the image data is mirrored horizontally.
+	///
+	/// The first column in memory is then the right-most column of the image.
+	/// The strides still describe the layout of the data in memory.
+	pub flip_x: bool,
+
+	/// If true, the image data is mirrored vertically.
+	///
+	/// The first row in memory is then the bottom row of the image,
+	/// as is common for BMP files or data read back from OpenGL.
+	/// The strides still describe the layout of the data in memory.
+	pub flip_y: bool,
 }
 
 /// Supported pixel formats.
@@ -26,17 +65,121 @@ pub enum PixelFormat {
 	/// 8-bit monochrome data with alpha.
 	MonoAlpha8(Alpha),
 
-	/// Interlaced 8-bit BGR data.
+	/// 8-bit BGR data.
 	Bgr8,
 
-	/// Interlaced 8-bit BGRA data.
+	/// 8-bit BGRA data.
 	Bgra8(Alpha),
 
-	/// Interlaced 8-bit RGB data.
+	/// 8-bit RGB data.
 	Rgb8,
 
-	/// Interlaced 8-bit RGBA data.
+	/// 8-bit RGBA data.
 	Rgba8(Alpha),
+
+	/// 16-bit monochrome data.
+	Mono16,
+
+	/// 16-bit monochrome data with alpha.
+	MonoAlpha16(Alpha),
+
+	/// 16-bit RGB data.
+	Rgb16,
+
+	/// 16-bit RGBA data.
+	Rgba16(Alpha),
+
+	/// 16-bit floating point monochrome data.
+	Mono16F,
+
+	/// 16-bit floating point RGB data.
+	Rgb16F,
+
+	/// 16-bit floating point RGBA data.
+	Rgba16F(Alpha),
+
+	/// 32-bit floating point monochrome data.
+	Mono32F,
+
+	/// Interlaced 32-bit floating point RGB data.
+	Rgb32F,
+
+	/// Interlaced 32-bit floating point RGBA data.
+	Rgba32F(Alpha),
+
+	/// 8-bit signed integer monochrome data.
+	Mono8I,
+
+	/// 16-bit signed integer monochrome data.
+	Mono16I,
+
+	/// 32-bit signed integer monochrome data.
+	Mono32I,
+
+	/// 8-bit YUV 4:2:0 data with a luma plane followed by an interleaved UV plane.
+	Nv12(YuvEncoding),
+
+	/// 8-bit YUV 4:2:0 data with a luma plane followed by an interleaved VU plane.
+	Nv21(YuvEncoding),
+
+	/// 8-bit YUV 4:2:0 data with a luma plane followed by separate U and V planes.
+	I420(YuvEncoding),
+
+	/// 8-bit packed YUV 4:2:2 data in the order Y0, U, Y1, V.
+	Yuyv(YuvEncoding),
+
+	/// 8-bit packed YUV 4:2:2 data in the order U, Y0, V, Y1.
+	Uyvy(YuvEncoding),
+
+	/// 8-bit raw Bayer mosaic data.
+	Bayer8(BayerPattern),
+
+	/// 16-bit raw Bayer mosaic data.
+	Bayer16(BayerPattern),
+
+	/// 8-bit label data, where each value is a class or instance ID.
+	///
+	/// Labels are shown using a palette of distinct colors.
+	/// Label 0 is treated as background and is shown transparent.
+	Label8,
+
+	/// 16-bit label data, where each value is a class or instance ID.
+	///
+	/// Labels are shown using a palette of distinct colors.
+	/// Label 0 is treated as background and is shown transparent.
+	Label16,
+
+	/// 32-bit label data, where each value is a class or instance ID.
+	///
+	/// Labels are shown using a palette of distinct colors.
+	/// Label 0 is treated as background and is shown transparent.
+	Label32,
+
+	/// 8-bit indices into a palette of up to 256 RGBA colors.
+	///
+	/// The palette is not part of the image info, but it is carried by the image itself.
+	/// See [`ImageView::with_palette`][crate::ImageView::with_palette].
+	/// Indices outside of the palette are shown transparent.
+	Indexed8,
+
+	/// 16-bit depth data in millimetres.
+	///
+	/// Depth images are shown using a colormap, where the value range gives the near and far distance in millimetres.
+	/// A value of zero indicates an invalid measurement, shown with [`WindowOptions::invalid_depth_color`][crate::WindowOptions::invalid_depth_color].
+	Depth16,
+
+	/// 32-bit floating point depth data in metres.
+	///
+	/// Depth images are shown using a colormap, where the value range gives the near and far distance in metres.
+	/// A value of zero or NaN indicates an invalid measurement, shown with [`WindowOptions::invalid_depth_color`][crate::WindowOptions::invalid_depth_color].
+	Depth32F,
+
+	/// Two-channel 32-bit floating point optical flow data, with the horizontal (u) and vertical (v) motion in pixels.
+	///
+	/// Flow images are shown with the standard HSV color-wheel encoding:
+	/// the hue gives the direction of motion and the saturation gives the magnitude.
+	/// The upper bound of the value range gives the magnitude that is shown fully saturated.
+	Flow32F,
 }
 
 /// Possible alpha representations.
@@ -51,23 +194,214 @@ pub enum Alpha {
 	Premultiplied,
 }
 
+/// The arrangement of the color filters in a Bayer mosaic.
+///
+/// The name lists the colors of the top-left 2x2 block, row by row.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum BayerPattern {
+	/// Red and green on the first row, green and blue on the second row.
+	Rggb,
+
+	/// Blue and green on the first row, green and red on the second row.
+	Bggr,
+
+	/// Green and red on the first row, blue and green on the second row.
+	Grbg,
+
+	/// Green and blue on the first row, red and green on the second row.
+	Gbrg,
+}
+
+/// The encoding of YUV data.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct YuvEncoding {
+	/// The matrix used to convert between RGB and YUV.
+	pub matrix: YuvMatrix,
+
+	/// The range of the encoded luma and chroma values.
+	pub range: YuvRange,
+}
+
+/// The matrix used to convert between RGB and YUV.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum YuvMatrix {
+	/// The matrix from ITU-R BT.601, commonly used for standard definition video and JPEG.
+	Bt601,
+
+	/// The matrix from ITU-R BT.709, commonly used for high definition video.
+	Bt709,
+}
+
+/// The range of encoded YUV values.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum YuvRange {
+	/// Luma and chroma use the full range from 0 to 255.
+	Full,
+
+	/// Luma uses the range 16 to 235 and chroma uses the range 16 to 240.
+	Limited,
+}
+
+/// The location of the chroma samples of YUV data.
+///
+/// The byte offset of the U sample of chroma position `(x, y)` is `offset_u + x * stride_x + y * stride_y`,
+/// and similarly for the V sample.
+/// For 4:2:0 data the chroma position of pixel `(x, y)` is `(x / 2, y / 2)`,
+/// for 4:2:2 data it is `(x / 2, y)`.
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct ChromaLayout {
+	/// The byte offset of the first U sample.
+	pub offset_u: u32,
+
+	/// The byte offset of the first V sample.
+	pub offset_v: u32,
+
+	/// The X stride of the chroma samples in bytes.
+	pub stride_x: u32,
+
+	/// The Y stride of the chroma samples in bytes.
+	pub stride_y: u32,
+}
+
+/// The range of sample values that is mapped to the full display range.
+///
+/// Sample values below the range are displayed as black, values above the range as full intensity.
+///
+/// Ranges compare equal if their floating point bounds have the same bit pattern,
+/// so `0.0` and `-0.0` are different bounds, and a NaN bound is equal to itself.
+#[derive(Copy, Clone, Debug)]
+pub enum ValueRange {
+	/// Use the full range of the sample type.
+	///
+	/// For unsigned integer samples, this maps zero to black and the maximum value of the type to full intensity.
+	/// For signed integer samples, this maps the minimum value of the type to black and the maximum value to full intensity.
+	/// For floating point samples, this maps 0.0 to black and 1.0 to full intensity.
+	Full,
+
+	/// Only the lowest `N` bits of each sample are used.
+	///
+	/// This maps zero to black and `2^N - 1` to full intensity.
+	/// For signed integer samples, this maps `-2^(N-1)` to black and `2^(N-1) - 1` to full intensity.
+	/// This is useful for 10, 12 or 14 bit data stored in 16 bit samples.
+	Bits(u8),
+
+	/// Map the given minimum and maximum sample values to black and full intensity.
+	///
+	/// If the minimum and maximum are equal, the range is widened to end at the minimum plus one.
+	MinMax(f32, f32),
+
+	/// Map the minimum and maximum sample value found in the image to black and full intensity.
+	///
+	/// The range is computed when the image is uploaded to the GPU.
+	/// Samples that are not finite (infinite or NaN) are ignored,
+	/// as are invalid measurements in depth images.
+	///
+	/// For optical flow images, the range goes from zero to the magnitude of the largest finite motion vector.
+	///
+	/// This is the default for signed integer, depth and optical flow images.
+	Auto,
+}
+
+/// The data type of the individual samples of a pixel.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub(crate) enum SampleType {
+	/// Unsigned 8-bit integers.
+	U8,
+
+	/// Unsigned 16-bit integers.
+	U16,
+
+	/// Unsigned 32-bit integers.
+	U32,
+
+	/// Signed 8-bit integers.
+	I8,
+
+	/// Signed 16-bit integers.
+	I16,
+
+	/// Signed 32-bit integers.
+	I32,
+
+	/// IEEE 754 half precision floating point numbers.
+	F16,
+
+	/// IEEE 754 single precision floating point numbers.
+	F32,
+}
+
+impl PartialEq for ValueRange {
+	fn eq(&self, other: &Self) -> bool {
+		match (self, other) {
+			(Self::Bits(a), Self::Bits(b)) => a == b,
+			(Self::MinMax(a_min, a_max), Self::MinMax(b_min, b_max)) => {
+				a_min.to_bits() == b_min.to_bits() && a_max.to_bits() == b_max.to_bits()
+			},
+			_ => std::mem::discriminant(self) == std::mem::discriminant(other),
+		}
+	}
+}
+
+impl Eq for ValueRange {}
+
 impl ImageInfo {
-	/// Create a new info struct with the given format, width and height.
+	/// Create a new info struct with the given format, width and height for interlaced data.
 	///
 	/// The row stride is automatically calculated based on the image width and pixel format.
 	/// If you wish to use a different row stride, construct the struct directly.
+	///
+	/// For YUV formats, the luma and chroma planes are stored directly after each other.
 	pub fn new(pixel_format: PixelFormat, width: u32, height: u32) -> Self {
+		let stride_c = u32::from(pixel_format.byte_depth());
 		let stride_x = u32::from(pixel_format.bytes_per_pixel());
+		let (stride_y, chroma) = match default_yuv_layout(pixel_format, width, height) {
+			Some((stride_y, chroma)) => (stride_y, chroma),
+			None => (stride_x * width, ChromaLayout::default()),
+		};
+		Self {
+			pixel_format,
+			width,
+			height,
+			stride_x,
+			stride_y,
+			stride_c,
+			value_range: pixel_format.default_value_range(),
+			chroma,
+			flip_x: false,
+			flip_y: false,
+		}
+	}
+
+	/// Create a new info struct with the given format, width and height for planar data.
+	///
+	/// Each channel is stored in a separate plane, one after the other.
+	/// The strides are automatically calculated based on the image size and pixel format.
+	/// If you wish to use different strides, construct the struct directly.
+	pub fn planar(pixel_format: PixelFormat, width: u32, height: u32) -> Self {
+		let stride_x = u32::from(pixel_format.byte_depth());
 		let stride_y = stride_x * width;
+		let stride_c = stride_y * height;
 		Self {
 			pixel_format,
 			width,
 			height,
 			stride_x,
 			stride_y,
+			stride_c,
+			value_range: pixel_format.default_value_range(),
+			chroma: ChromaLayout::default(),
+			flip_x: false,
+			flip_y: false,
 		}
 	}
 
+	/// Check if the image data is planar.
+	///
+	/// Image data is considered planar if the channel stride is larger than the X and Y stride.
+	pub fn is_planar(&self) -> bool {
+		self.pixel_format.channels() > 1 && self.stride_c > self.stride_x && self.stride_c > self.stride_y
+	}
+
 	/// Create a new info struct for an 8-bit monochrome image with the given width and height.
 	pub fn mono8(width: u32, height: u32) -> Self {
 		Self::new(PixelFormat::Mono8, width, height)
@@ -113,12 +447,308 @@ impl ImageInfo {
 		Self::new(PixelFormat::Rgba8(Alpha::Premultiplied), width, height)
 	}
 
+	/// Create a new info struct for a 16-bit monochrome image with the given width and height.
+	pub fn mono16(width: u32, height: u32) -> Self {
+		Self::new(PixelFormat::Mono16, width, height)
+	}
+
+	/// Create a new info struct for a 16-bit monochrome image with with alpha channel and the given width and height.
+	pub fn mono_alpha16(width: u32, height: u32) -> Self {
+		Self::new(PixelFormat::MonoAlpha16(Alpha::Unpremultiplied), width, height)
+	}
+
+	/// Create a new info struct for a 16-bit monochrome image with premultiplied alpha channel and the given width and height.
+	pub fn mono_alpha16_premultiplied(width: u32, height: u32) -> Self {
+		Self::new(PixelFormat::MonoAlpha16(Alpha::Premultiplied), width, height)
+	}
+
+	/// Create a new info struct for a 16-bit RGB image with the given width and height.
+	pub fn rgb16(width: u32, height: u32) -> Self {
+		Self::new(PixelFormat::Rgb16, width, height)
+	}
+
+	/// Create a new info struct for a 16-bit RGBA image with the given width and height.
+	pub fn rgba16(width: u32, height: u32) -> Self {
+		Self::new(PixelFormat::Rgba16(Alpha::Unpremultiplied), width, height)
+	}
+
+	/// Create a new info struct for a 16-bit RGBA image with premultiplied alpha channel and the given width and height.
+	pub fn rgba16_premultiplied(width: u32, height: u32) -> Self {
+		Self::new(PixelFormat::Rgba16(Alpha::Premultiplied), width, height)
+	}
+
+	/// Create a new info struct for a 16-bit floating point monochrome image with the given width and height.
+	pub fn mono16f(width: u32, height: u32) -> Self {
+		Self::new(PixelFormat::Mono16F, width, height)
+	}
+
+	/// Create a new info struct for a 16-bit floating point RGB image with the given width and height.
+	pub fn rgb16f(width: u32, height: u32) -> Self {
+		Self::new(PixelFormat::Rgb16F, width, height)
+	}
+
+	/// Create a new info struct for a 16-bit floating point RGBA image with the given width and height.
+	pub fn rgba16f(width: u32, height: u32) -> Self {
+		Self::new(PixelFormat::Rgba16F(Alpha::Unpremultiplied), width, height)
+	}
+
+	/// Create a new info struct for a 16-bit floating point RGBA image with premultiplied alpha channel and the given width and height.
+	pub fn rgba16f_premultiplied(width: u32, height: u32) -> Self {
+		Self::new(PixelFormat::Rgba16F(Alpha::Premultiplied), width, height)
+	}
+
+	/// Create a new info struct for a 32-bit floating point monochrome image with the given width and height.
+	pub fn mono32f(width: u32, height: u32) -> Self {
+		Self::new(PixelFormat::Mono32F, width, height)
+	}
+
+	/// Create a new info struct for a 32-bit floating point RGB image with the given width and height.
+	pub fn rgb32f(width: u32, height: u32) -> Self {
+		Self::new(PixelFormat::Rgb32F, width, height)
+	}
+
+	/// Create a new info struct for a 32-bit floating point RGBA image with the given width and height.
+	pub fn rgba32f(width: u32, height: u32) -> Self {
+		Self::new(PixelFormat::Rgba32F(Alpha::Unpremultiplied), width, height)
+	}
+
+	/// Create a new info struct for a 32-bit floating point RGBA image with premultiplied alpha channel and the given width and height.
+	pub fn rgba32f_premultiplied(width: u32, height: u32) -> Self {
+		Self::new(PixelFormat::Rgba32F(Alpha::Premultiplied), width, height)
+	}
+
+	/// Create a new info struct for an 8-bit signed integer monochrome image with the given width and height.
+	pub fn mono8i(width: u32, height: u32) -> Self {
+		Self::new(PixelFormat::Mono8I, width, height)
+	}
+
+	/// Create a new info struct for a 16-bit signed integer monochrome image with the given width and height.
+	pub fn mono16i(width: u32, height: u32) -> Self {
+		Self::new(PixelFormat::Mono16I, width, height)
+	}
+
+	/// Create a new info struct for a 32-bit signed integer monochrome image with the given width and height.
+	pub fn mono32i(width: u32, height: u32) -> Self {
+		Self::new(PixelFormat::Mono32I, width, height)
+	}
+
+	/// Create a new info struct for an NV12 image with the given encoding, width and height.
+	pub fn nv12(width: u32, height: u32, encoding: YuvEncoding) -> Self {
+		Self::new(PixelFormat::Nv12(encoding), width, height)
+	}
+
+	/// Create a new info struct for an NV21 image with the given encoding, width and height.
+	pub fn nv21(width: u32, height: u32, encoding: YuvEncoding) -> Self {
+		Self::new(PixelFormat::Nv21(encoding), width, height)
+	}
+
+	/// Create a new info struct for an I420 image with the given encoding, width and height.
+	pub fn i420(width: u32, height: u32, encoding: YuvEncoding) -> Self {
+		Self::new(PixelFormat::I420(encoding), width, height)
+	}
+
+	/// Create a new info struct for a YUYV image with the given encoding, width and height.
+	pub fn yuyv(width: u32, height: u32, encoding: YuvEncoding) -> Self {
+		Self::new(PixelFormat::Yuyv(encoding), width, height)
+	}
+
+	/// Create a new info struct for a UYVY image with the given encoding, width and height.
+	pub fn uyvy(width: u32, height: u32, encoding: YuvEncoding) -> Self {
+		Self::new(PixelFormat::Uyvy(encoding), width, height)
+	}
+
+	/// Create a new info struct for an 8-bit Bayer mosaic with the given pattern, width and height.
+	pub fn bayer8(width: u32, height: u32, pattern: BayerPattern) -> Self {
+		Self::new(PixelFormat::Bayer8(pattern), width, height)
+	}
+
+	/// Create a new info struct for a 16-bit Bayer mosaic with the given pattern, width and height.
+	pub fn bayer16(width: u32, height: u32, pattern: BayerPattern) -> Self {
+		Self::new(PixelFormat::Bayer16(pattern), width, height)
+	}
+
+	/// Create a new info struct for an 8-bit label image with the given width and height.
+	pub fn label8(width: u32, height: u32) -> Self {
+		Self::new(PixelFormat::Label8, width, height)
+	}
+
+	/// Create a new info struct for a 16-bit label image with the given width and height.
+	pub fn label16(width: u32, height: u32) -> Self {
+		Self::new(PixelFormat::Label16, width, height)
+	}
+
+	/// Create a new info struct for a 32-bit label image with the given width and height.
+	pub fn label32(width: u32, height: u32) -> Self {
+		Self::new(PixelFormat::Label32, width, height)
+	}
+
+	/// Create a new info struct for an 8-bit indexed image with the given width and height.
+	pub fn indexed8(width: u32, height: u32) -> Self {
+		Self::new(PixelFormat::Indexed8, width, height)
+	}
+
+	/// Create a new info struct for a 16-bit depth image in millimetres with the given width and height.
+	pub fn depth16(width: u32, height: u32) -> Self {
+		Self::new(PixelFormat::Depth16, width, height)
+	}
+
+	/// Create a new info struct for a 32-bit floating point depth image in metres with the given width and height.
+	pub fn depth32f(width: u32, height: u32) -> Self {
+		Self::new(PixelFormat::Depth32F, width, height)
+	}
+
+	/// Create a new info struct for a two-channel 32-bit floating point optical flow image with the given width and height.
+	pub fn flow32f(width: u32, height: u32) -> Self {
+		Self::new(PixelFormat::Flow32F, width, height)
+	}
+
 	/// Get the image size in bytes.
 	pub fn byte_size(self) -> u64 {
-		if self.stride_y >= self.stride_x {
-			u64::from(self.stride_y) * u64::from(self.height)
-		} else {
-			u64::from(self.stride_x) * u64::from(self.width)
+		let size_x = u64::from(self.stride_x) * u64::from(self.width);
+		let size_y = u64::from(self.stride_y) * u64::from(self.height);
+		let size_c = u64::from(self.stride_c) * u64::from(self.pixel_format.channels());
+		let size = size_x.max(size_y).max(size_c);
+
+		match self.chroma_size() {
+			Some((width, height)) if width > 0 && height > 0 => {
+				let offset = u64::from(self.chroma.offset_u.max(self.chroma.offset_v));
+				let last_x = u64::from(self.chroma.stride_x) * u64::from(width - 1);
+				let last_y = u64::from(self.chroma.stride_y) * u64::from(height - 1);
+				size.max(offset + last_x + last_y + 1)
+			},
+			_ => size,
+		}
+	}
+
+	/// Check that the image info is valid for a data buffer of the given size.
+	///
+	/// This checks that the image is not empty,
+	/// that the strides do not cause samples to overlap,
+	/// and that all samples lie inside the data buffer.
+	pub fn validate(&self, data_size: usize) -> Result<(), ImageDataError> {
+		if self.width == 0 || self.height == 0 {
+			return Err(ZeroSize { width: self.width, height: self.height }.into());
+		}
+
+		let required = self.required_size()?;
+		if (data_size as u64) < required {
+			return Err(BufferTooSmall { required, actual: data_size as u64 }.into());
+		}
+		Ok(())
+	}
+
+	/// Get the number of bytes spanned by the samples of the image.
+	///
+	/// Padding after the last sample is not included.
+	/// Returns an error if the strides cause samples to overlap.
+	pub(crate) fn required_size(&self) -> Result<u64, OverlappingStrides> {
+		let required = match self.chroma_size() {
+			Some((chroma_width, chroma_height)) => {
+				let luma = check_strides(
+					&[("X stride", self.stride_x, self.width), ("Y stride", self.stride_y, self.height)],
+					u64::from(self.pixel_format.bytes_per_pixel()),
+				)?;
+				let chroma = check_strides(
+					&[
+						("chroma X stride", self.chroma.stride_x, chroma_width),
+						("chroma Y stride", self.chroma.stride_y, chroma_height),
+					],
+					1,
+				)?;
+				let chroma_offset = u64::from(self.chroma.offset_u.max(self.chroma.offset_v));
+				luma.max(chroma_offset + chroma)
+			},
+			None => check_strides(
+				&[
+					("X stride", self.stride_x, self.width),
+					("Y stride", self.stride_y, self.height),
+					("channel stride", self.stride_c, u32::from(self.pixel_format.channels())),
+				],
+				u64::from(self.pixel_format.byte_depth()),
+			)?,
+		};
+		Ok(required)
+	}
+
+	/// Get the location in memory of the pixel at `(x, y)` in the image.
+	///
+	/// This takes into account the [`Self::flip_x`] and [`Self::flip_y`] flags.
+	///
+	/// Returns [`None`] if the position lies outside of the image.
+	pub(crate) fn memory_position(&self, x: u32, y: u32) -> Option<(u32, u32)> {
+		if x >= self.width || y >= self.height {
+			return None;
+		}
+		let x = if self.flip_x { self.width - 1 - x } else { x };
+		let y = if self.flip_y { self.height - 1 - y } else { y };
+		Some((x, y))
+	}
+
+	/// Get the image info and data offset for a region of the image.
+	///
+	/// The region is clipped to the image.
+	/// For subsampled YUV formats, the region is extended to start at a whole chroma sample.
+	/// For Bayer formats, the pattern is adjusted to the start of the region.
+	///
+	/// Returns the image info of the region and the offset of the region in the image data.
+	pub(crate) fn crop(&self, area: &Rectangle) -> (ImageInfo, usize) {
+		let clip = |start: i32, size: u32, max: u32| {
+			let end = (i64::from(start) + i64::from(size)).clamp(0, i64::from(max)) as u32;
+			let start = i64::from(start).clamp(0, i64::from(max)) as u32;
+			(start, end.max(start))
+		};
+		let (x_start, x_end) = clip(area.x(), area.width(), self.width);
+		let (y_start, y_end) = clip(area.y(), area.height(), self.height);
+
+		// Work with the location of the region in memory from here on.
+		let (mut x_start, x_end) = if self.flip_x { (self.width - x_end, self.width - x_start) } else { (x_start, x_end) };
+		let (mut y_start, y_end) = if self.flip_y { (self.height - y_end, self.height - y_start) } else { (y_start, y_end) };
+
+		let mut info = *self;
+		let chroma_start = match self.pixel_format {
+			PixelFormat::Nv12(_) | PixelFormat::Nv21(_) | PixelFormat::I420(_) => {
+				x_start -= x_start % 2;
+				y_start -= y_start % 2;
+				Some((x_start / 2, y_start / 2))
+			},
+			PixelFormat::Yuyv(_) | PixelFormat::Uyvy(_) => {
+				x_start -= x_start % 2;
+				Some((x_start / 2, y_start))
+			},
+			PixelFormat::Bayer8(pattern) => {
+				info.pixel_format = PixelFormat::Bayer8(pattern.shifted(x_start, y_start));
+				None
+			},
+			PixelFormat::Bayer16(pattern) => {
+				info.pixel_format = PixelFormat::Bayer16(pattern.shifted(x_start, y_start));
+				None
+			},
+			_ => None,
+		};
+
+		info.width = x_end - x_start;
+		info.height = y_end - y_start;
+		let offset = x_start * self.stride_x + y_start * self.stride_y;
+
+		if let Some((chroma_x, chroma_y)) = chroma_start {
+			let chroma = chroma_x * self.chroma.stride_x + chroma_y * self.chroma.stride_y;
+			// Layouts with the chroma samples before the luma samples can not be cropped correctly.
+			info.chroma.offset_u = (self.chroma.offset_u + chroma).saturating_sub(offset);
+			info.chroma.offset_v = (self.chroma.offset_v + chroma).saturating_sub(offset);
+		}
+
+		(info, offset as usize)
+	}
+
+	/// Get the size of the chroma planes for YUV formats.
+	///
+	/// Returns [`None`] if the pixel format is not a YUV format.
+	pub(crate) fn chroma_size(self) -> Option<(u32, u32)> {
+		let width = self.width / 2 + self.width % 2;
+		match self.pixel_format {
+			PixelFormat::Nv12(_) | PixelFormat::Nv21(_) | PixelFormat::I420(_) => Some((width, self.height / 2 + self.height % 2)),
+			PixelFormat::Yuyv(_) | PixelFormat::Uyvy(_) => Some((width, self.height)),
+			_ => None,
 		}
 	}
 }
@@ -128,22 +758,103 @@ impl PixelFormat {
 	pub fn channels(self) -> u8 {
 		match self {
 			PixelFormat::Mono8 => 1,
-			PixelFormat::MonoAlpha8(_) => 1,
+			PixelFormat::MonoAlpha8(_) => 2,
 			PixelFormat::Bgr8 => 3,
 			PixelFormat::Bgra8(_) => 4,
 			PixelFormat::Rgb8 => 3,
 			PixelFormat::Rgba8(_) => 4,
+			PixelFormat::Mono16 => 1,
+			PixelFormat::MonoAlpha16(_) => 2,
+			PixelFormat::Rgb16 => 3,
+			PixelFormat::Rgba16(_) => 4,
+			PixelFormat::Mono16F => 1,
+			PixelFormat::Rgb16F => 3,
+			PixelFormat::Rgba16F(_) => 4,
+			PixelFormat::Mono32F => 1,
+			PixelFormat::Rgb32F => 3,
+			PixelFormat::Rgba32F(_) => 4,
+			PixelFormat::Mono8I => 1,
+			PixelFormat::Mono16I => 1,
+			PixelFormat::Mono32I => 1,
+			PixelFormat::Nv12(_) => 3,
+			PixelFormat::Nv21(_) => 3,
+			PixelFormat::I420(_) => 3,
+			PixelFormat::Yuyv(_) => 3,
+			PixelFormat::Uyvy(_) => 3,
+			PixelFormat::Bayer8(_) => 1,
+			PixelFormat::Bayer16(_) => 1,
+			PixelFormat::Label8 => 1,
+			PixelFormat::Label16 => 1,
+			PixelFormat::Label32 => 1,
+			PixelFormat::Indexed8 => 1,
+			PixelFormat::Depth16 => 1,
+			PixelFormat::Depth32F => 1,
+			PixelFormat::Flow32F => 2,
 		}
 	}
 
 	/// Get the bytes per channel.
-	const fn byte_depth(self) -> u8 {
-		1
+	pub fn byte_depth(self) -> u8 {
+		match self.sample_type() {
+			SampleType::U8 => 1,
+			SampleType::U16 => 2,
+			SampleType::U32 => 4,
+			SampleType::I8 => 1,
+			SampleType::I16 => 2,
+			SampleType::I32 => 4,
+			SampleType::F16 => 2,
+			SampleType::F32 => 4,
+		}
+	}
+
+	/// Get the data type of the samples.
+	pub(crate) fn sample_type(self) -> SampleType {
+		match self {
+			PixelFormat::Mono8 => SampleType::U8,
+			PixelFormat::MonoAlpha8(_) => SampleType::U8,
+			PixelFormat::Bgr8 => SampleType::U8,
+			PixelFormat::Bgra8(_) => SampleType::U8,
+			PixelFormat::Rgb8 => SampleType::U8,
+			PixelFormat::Rgba8(_) => SampleType::U8,
+			PixelFormat::Mono16 => SampleType::U16,
+			PixelFormat::MonoAlpha16(_) => SampleType::U16,
+			PixelFormat::Rgb16 => SampleType::U16,
+			PixelFormat::Rgba16(_) => SampleType::U16,
+			PixelFormat::Mono16F => SampleType::F16,
+			PixelFormat::Rgb16F => SampleType::F16,
+			PixelFormat::Rgba16F(_) => SampleType::F16,
+			PixelFormat::Mono32F => SampleType::F32,
+			PixelFormat::Rgb32F => SampleType::F32,
+			PixelFormat::Rgba32F(_) => SampleType::F32,
+			PixelFormat::Mono8I => SampleType::I8,
+			PixelFormat::Mono16I => SampleType::I16,
+			PixelFormat::Mono32I => SampleType::I32,
+			PixelFormat::Nv12(_) => SampleType::U8,
+			PixelFormat::Nv21(_) => SampleType::U8,
+			PixelFormat::I420(_) => SampleType::U8,
+			PixelFormat::Yuyv(_) => SampleType::U8,
+			PixelFormat::Uyvy(_) => SampleType::U8,
+			PixelFormat::Bayer8(_) => SampleType::U8,
+			PixelFormat::Bayer16(_) => SampleType::U16,
+			PixelFormat::Label8 => SampleType::U8,
+			PixelFormat::Label16 => SampleType::U16,
+			PixelFormat::Label32 => SampleType::U32,
+			PixelFormat::Indexed8 => SampleType::U8,
+			PixelFormat::Depth16 => SampleType::U16,
+			PixelFormat::Depth32F => SampleType::F32,
+			PixelFormat::Flow32F => SampleType::F32,
+		}
 	}
 
 	/// Get the bytes per pixel.
+	///
+	/// For YUV formats this is the distance between luma samples.
 	pub fn bytes_per_pixel(self) -> u8 {
-		self.byte_depth() * self.channels()
+		match self {
+			PixelFormat::Nv12(_) | PixelFormat::Nv21(_) | PixelFormat::I420(_) => 1,
+			PixelFormat::Yuyv(_) | PixelFormat::Uyvy(_) => 2,
+			_ => self.byte_depth() * self.channels(),
+		}
 	}
 
 	/// Get the alpha representation of the pixel format.
@@ -157,6 +868,358 @@ impl PixelFormat {
 			PixelFormat::Bgra8(a) => Some(a),
 			PixelFormat::Rgb8 => None,
 			PixelFormat::Rgba8(a) => Some(a),
+			PixelFormat::Mono16 => None,
+			PixelFormat::MonoAlpha16(a) => Some(a),
+			PixelFormat::Rgb16 => None,
+			PixelFormat::Rgba16(a) => Some(a),
+			PixelFormat::Mono16F => None,
+			PixelFormat::Rgb16F => None,
+			PixelFormat::Rgba16F(a) => Some(a),
+			PixelFormat::Mono32F => None,
+			PixelFormat::Rgb32F => None,
+			PixelFormat::Rgba32F(a) => Some(a),
+			PixelFormat::Mono8I => None,
+			PixelFormat::Mono16I => None,
+			PixelFormat::Mono32I => None,
+			PixelFormat::Nv12(_) => None,
+			PixelFormat::Nv21(_) => None,
+			PixelFormat::I420(_) => None,
+			PixelFormat::Yuyv(_) => None,
+			PixelFormat::Uyvy(_) => None,
+			PixelFormat::Bayer8(_) => None,
+			PixelFormat::Bayer16(_) => None,
+			PixelFormat::Label8 => None,
+			PixelFormat::Label16 => None,
+			PixelFormat::Label32 => None,
+			PixelFormat::Indexed8 => None,
+			PixelFormat::Depth16 => None,
+			PixelFormat::Depth32F => None,
+			PixelFormat::Flow32F => None,
+		}
+	}
+
+	/// Check if the pixel format contains depth data.
+	pub fn is_depth(self) -> bool {
+		matches!(self, PixelFormat::Depth16 | PixelFormat::Depth32F)
+	}
+
+	/// Get the default value range for the pixel format.
+	///
+	/// Signed data is rarely spread over the full range of the sample type, so it uses the range of the data by default.
+	fn default_value_range(self) -> ValueRange {
+		let signed = matches!(self.sample_type(), SampleType::I8 | SampleType::I16 | SampleType::I32);
+		if signed || self.is_depth() || self == PixelFormat::Flow32F {
+			ValueRange::Auto
+		} else {
+			ValueRange::Full
+		}
+	}
+
+	/// Check if the pixel format contains label data.
+	pub fn is_label(self) -> bool {
+		matches!(self, PixelFormat::Label8 | PixelFormat::Label16 | PixelFormat::Label32)
+	}
+
+	/// Get the Bayer pattern of the pixel format.
+	///
+	/// Returns [`None`], if the pixel format is not a Bayer format.
+	pub fn bayer_pattern(self) -> Option<BayerPattern> {
+		match self {
+			PixelFormat::Bayer8(p) => Some(p),
+			PixelFormat::Bayer16(p) => Some(p),
+			_ => None,
+		}
+	}
+
+	/// Get the YUV encoding of the pixel format.
+	///
+	/// Returns [`None`], if the pixel format is not a YUV format.
+	pub fn yuv_encoding(self) -> Option<YuvEncoding> {
+		match self {
+			PixelFormat::Nv12(e) => Some(e),
+			PixelFormat::Nv21(e) => Some(e),
+			PixelFormat::I420(e) => Some(e),
+			PixelFormat::Yuyv(e) => Some(e),
+			PixelFormat::Uyvy(e) => Some(e),
+			_ => None,
 		}
 	}
 }
+
+impl BayerPattern {
+	/// Get the pattern of a mosaic that starts at the given position in this mosaic.
+	fn shifted(self, x: u32, y: u32) -> Self {
+		let (red_x, red_y) = match self {
+			BayerPattern::Rggb => (0, 0),
+			BayerPattern::Grbg => (1, 0),
+			BayerPattern::Gbrg => (0, 1),
+			BayerPattern::Bggr => (1, 1),
+		};
+		match ((red_x + x) % 2, (red_y + y) % 2) {
+			(0, 0) => BayerPattern::Rggb,
+			(1, 0) => BayerPattern::Grbg,
+			(0, _) => BayerPattern::Gbrg,
+			_ => BayerPattern::Bggr,
+		}
+	}
+}
+
+impl YuvEncoding {
+	/// Create a new YUV encoding from a matrix and a value range.
+	pub const fn new(matrix: YuvMatrix, range: YuvRange) -> Self {
+		Self { matrix, range }
+	}
+}
+
+/// Check that the strides of the given dimensions do not cause samples to overlap.
+///
+/// Each dimension is given as a tuple of the name of the stride, the stride and the size of the dimension.
+/// Returns the number of bytes spanned by all samples.
+fn check_strides(dimensions: &[(&'static str, u32, u32)], sample_size: u64) -> Result<u64, OverlappingStrides> {
+	// Dimensions with only one element do not affect the layout.
+	let mut dimensions: Vec<_> = dimensions.iter().filter(|(_, _, size)| *size > 1).collect();
+	dimensions.sort_by_key(|(_, stride, _)| *stride);
+
+	// Each stride must skip over everything spanned by the smaller strides.
+	let mut extent = sample_size;
+	for &&(name, stride, size) in &dimensions {
+		if u64::from(stride) < extent {
+			return Err(OverlappingStrides { stride: name, value: stride, minimum: extent });
+		}
+		extent += u64::from(stride) * u64::from(size - 1);
+	}
+	Ok(extent)
+}
+
+/// Get the default row stride and chroma layout for YUV formats without padding.
+///
+/// Returns [`None`] if the pixel format is not a YUV format.
+fn default_yuv_layout(pixel_format: PixelFormat, width: u32, height: u32) -> Option<(u32, ChromaLayout)> {
+	let luma_size = width * height;
+	let chroma_width = width / 2 + width % 2;
+	let chroma_height = height / 2 + height % 2;
+	match pixel_format {
+		PixelFormat::Nv12(_) => Some((width, ChromaLayout {
+			offset_u: luma_size,
+			offset_v: luma_size + 1,
+			stride_x: 2,
+			stride_y: 2 * chroma_width,
+		})),
+		PixelFormat::Nv21(_) => Some((width, ChromaLayout {
+			offset_u: luma_size + 1,
+			offset_v: luma_size,
+			stride_x: 2,
+			stride_y: 2 * chroma_width,
+		})),
+		PixelFormat::I420(_) => Some((width, ChromaLayout {
+			offset_u: luma_size,
+			offset_v: luma_size + chroma_width * chroma_height,
+			stride_x: 1,
+			stride_y: chroma_width,
+		})),
+		PixelFormat::Yuyv(_) => Some((4 * chroma_width, ChromaLayout {
+			offset_u: 1,
+			offset_v: 3,
+			stride_x: 4,
+			stride_y: 4 * chroma_width,
+		})),
+		PixelFormat::Uyvy(_) => Some((4 * chroma_width, ChromaLayout {
+			offset_u: 0,
+			offset_v: 2,
+			stride_x: 4,
+			stride_y: 4 * chroma_width,
+		})),
+		_ => None,
+	}
+}
+
+#[cfg(test)]
+mod test {
+	use super::*;
+	use assert2::assert;
+
+	#[test]
+	fn default_strides() {
+		assert!(ImageInfo::mono8(10, 20).stride_x == 1);
+		assert!(ImageInfo::mono8(10, 20).stride_y == 10);
+		assert!(ImageInfo::mono_alpha8(10, 20).stride_x == 2);
+		assert!(ImageInfo::rgb8(10, 20).stride_y == 30);
+		assert!(ImageInfo::mono16(10, 20).stride_x == 2);
+		assert!(ImageInfo::mono_alpha16(10, 20).stride_x == 4);
+		assert!(ImageInfo::rgb16(10, 20).stride_x == 6);
+		assert!(ImageInfo::rgba16(10, 20).stride_y == 80);
+		assert!(ImageInfo::rgba16(10, 20).byte_size() == 1600);
+		assert!(ImageInfo::mono16f(10, 20).stride_x == 2);
+		assert!(ImageInfo::rgb32f(10, 20).stride_x == 12);
+		assert!(ImageInfo::rgba32f(10, 20).stride_y == 160);
+		assert!(ImageInfo::rgba32f(10, 20).stride_c == 4);
+		assert!(!ImageInfo::rgba32f(10, 20).is_planar());
+		assert!(ImageInfo::bayer8(10, 20, BayerPattern::Rggb).stride_y == 10);
+		assert!(ImageInfo::bayer16(10, 20, BayerPattern::Gbrg).stride_y == 20);
+		assert!(ImageInfo::label8(10, 20).stride_y == 10);
+		assert!(ImageInfo::label32(10, 20).stride_y == 40);
+	}
+
+	#[test]
+	fn default_value_range() {
+		assert!(ImageInfo::mono8(10, 20).value_range == ValueRange::Full);
+		assert!(ImageInfo::mono16(10, 20).value_range == ValueRange::Full);
+		assert!(ImageInfo::rgb32f(10, 20).value_range == ValueRange::Full);
+		assert!(ImageInfo::mono8i(10, 20).value_range == ValueRange::Auto);
+		assert!(ImageInfo::mono16i(10, 20).value_range == ValueRange::Auto);
+		assert!(ImageInfo::mono32i(10, 20).value_range == ValueRange::Auto);
+		assert!(ImageInfo::depth16(10, 20).value_range == ValueRange::Auto);
+		assert!(ImageInfo::flow32f(10, 20).value_range == ValueRange::Auto);
+	}
+
+	#[test]
+	fn planar_strides() {
+		let info = ImageInfo::planar(PixelFormat::Rgb8, 10, 20);
+		assert!(info.stride_x == 1);
+		assert!(info.stride_y == 10);
+		assert!(info.stride_c == 200);
+		assert!(info.byte_size() == 600);
+		assert!(info.is_planar());
+
+		let info = ImageInfo::planar(PixelFormat::Rgba16(Alpha::Unpremultiplied), 10, 20);
+		assert!(info.stride_x == 2);
+		assert!(info.stride_y == 20);
+		assert!(info.stride_c == 400);
+		assert!(info.byte_size() == 1600);
+
+		// Single channel images are never planar.
+		assert!(!ImageInfo::planar(PixelFormat::Mono8, 10, 20).is_planar());
+	}
+
+	#[test]
+	fn value_range_eq() {
+		assert!(ValueRange::Full == ValueRange::Full);
+		assert!(ValueRange::Bits(10) == ValueRange::Bits(10));
+		assert!(ValueRange::Bits(10) != ValueRange::Bits(12));
+		assert!(ValueRange::MinMax(0.0, 1.0) == ValueRange::MinMax(0.0, 1.0));
+		assert!(ValueRange::MinMax(0.0, 1.0) != ValueRange::MinMax(-0.0, 1.0));
+		assert!(ValueRange::MinMax(f32::NAN, 1.0) == ValueRange::MinMax(f32::NAN, 1.0));
+		assert!(ValueRange::Full != ValueRange::Bits(8));
+	}
+
+	#[test]
+	fn memory_position() {
+		let info = ImageInfo::rgb8(10, 20);
+		assert!(info.memory_position(2, 3) == Some((2, 3)));
+		let info = ImageInfo { flip_y: true, ..info };
+		assert!(info.memory_position(2, 3) == Some((2, 16)));
+		let info = ImageInfo { flip_x: true, ..info };
+		assert!(info.memory_position(2, 3) == Some((7, 16)));
+		assert!(info.memory_position(9, 19) == Some((0, 0)));
+
+		// Positions outside of the image have no memory position.
+		assert!(info.memory_position(10, 3) == None);
+		assert!(info.memory_position(2, 20) == None);
+		assert!(ImageInfo::rgb8(10, 20).memory_position(10, 20) == None);
+	}
+
+	#[test]
+	fn crop() {
+		let info = ImageInfo::rgb8(10, 20);
+		let (cropped, offset) = info.crop(&Rectangle::from_xywh(2, 3, 4, 5));
+		assert!(cropped == ImageInfo { width: 4, height: 5, ..info });
+		assert!(offset == 3 * 30 + 2 * 3);
+
+		// The region is clipped to the image.
+		let (cropped, offset) = info.crop(&Rectangle::from_xywh(-2, 18, 4, 5));
+		assert!(cropped == ImageInfo { width: 2, height: 2, ..info });
+		assert!(offset == 18 * 30);
+		let (cropped, _) = info.crop(&Rectangle::from_xywh(20, 30, 4, 5));
+		assert!(cropped.width == 0);
+		assert!(cropped.height == 0);
+
+		// Flipped images are cropped in image coordinates.
+		let info = ImageInfo { flip_y: true, ..info };
+		let (cropped, offset) = info.crop(&Rectangle::from_xywh(2, 3, 4, 5));
+		assert!(cropped == ImageInfo { width: 4, height: 5, ..info });
+		assert!(offset == 12 * 30 + 2 * 3);
+
+		// The Bayer pattern depends on the start of the region.
+		let (cropped, offset) = ImageInfo::bayer8(10, 20, BayerPattern::Rggb).crop(&Rectangle::from_xywh(1, 1, 4, 4));
+		assert!(cropped.pixel_format == PixelFormat::Bayer8(BayerPattern::Bggr));
+		assert!(offset == 11);
+		let (cropped, _) = ImageInfo::bayer8(10, 20, BayerPattern::Gbrg).crop(&Rectangle::from_xywh(1, 0, 4, 4));
+		assert!(cropped.pixel_format == PixelFormat::Bayer8(BayerPattern::Bggr));
+
+		// YUV 4:2:0 regions start at an even position.
+		let encoding = YuvEncoding::new(YuvMatrix::Bt601, YuvRange::Limited);
+		let (cropped, offset) = ImageInfo::nv12(10, 20, encoding).crop(&Rectangle::from_xywh(3, 5, 4, 4));
+		assert!(cropped.width == 5);
+		assert!(cropped.height == 5);
+		assert!(offset == 4 * 10 + 2);
+		assert!(cropped.chroma.offset_u == 200 + 2 * 10 + 2 - 42);
+		assert!(cropped.chroma.offset_v == 201 + 2 * 10 + 2 - 42);
+		assert!(let Ok(()) = cropped.validate(300 - 42));
+	}
+
+	#[test]
+	fn validate() {
+		assert!(let Ok(()) = ImageInfo::rgb8(10, 20).validate(600));
+		assert!(let Ok(()) = ImageInfo::planar(PixelFormat::Rgb8, 10, 20).validate(600));
+		assert!(let Err(ImageDataError::ZeroSize(_)) = ImageInfo::rgb8(0, 20).validate(600));
+		assert!(let Err(ImageDataError::ZeroSize(_)) = ImageInfo::rgb8(10, 0).validate(600));
+
+		let error = ImageInfo::rgb8(10, 20).validate(599);
+		assert!(let Err(ImageDataError::BufferTooSmall(BufferTooSmall { required: 600, actual: 599 })) = error);
+
+		// Padding after the last row is not required.
+		let info = ImageInfo { stride_y: 32, ..ImageInfo::rgb8(10, 20) };
+		assert!(let Ok(()) = info.validate(32 * 19 + 30));
+		assert!(let Err(ImageDataError::BufferTooSmall(_)) = info.validate(32 * 19 + 29));
+
+		let info = ImageInfo { stride_y: 29, ..ImageInfo::rgb8(10, 20) };
+		let error = info.validate(600);
+		assert!(let Err(ImageDataError::OverlappingStrides(OverlappingStrides { stride: "Y stride", value: 29, minimum: 30 })) = error);
+
+		let info = ImageInfo { stride_c: 0, ..ImageInfo::rgb8(10, 20) };
+		assert!(let Err(ImageDataError::OverlappingStrides(OverlappingStrides { stride: "channel stride", .. })) = info.validate(600));
+
+		// Strides of dimensions with a single element do not matter.
+		let info = ImageInfo { stride_y: 0, ..ImageInfo::rgb8(10, 1) };
+		assert!(let Ok(()) = info.validate(30));
+
+		let encoding = YuvEncoding::new(YuvMatrix::Bt709, YuvRange::Full);
+		assert!(let Ok(()) = ImageInfo::nv12(10, 20, encoding).validate(300));
+		assert!(let Err(ImageDataError::BufferTooSmall(_)) = ImageInfo::nv12(10, 20, encoding).validate(299));
+		assert!(let Ok(()) = ImageInfo::uyvy(10, 20, encoding).validate(400));
+	}
+
+	#[test]
+	fn yuv_layouts() {
+		let encoding = YuvEncoding::new(YuvMatrix::Bt601, YuvRange::Limited);
+
+		let info = ImageInfo::nv12(10, 20, encoding);
+		assert!(info.stride_x == 1);
+		assert!(info.stride_y == 10);
+		assert!(info.chroma == ChromaLayout { offset_u: 200, offset_v: 201, stride_x: 2, stride_y: 10 });
+		assert!(info.byte_size() == 300);
+
+		let info = ImageInfo::nv21(10, 20, encoding);
+		assert!(info.chroma == ChromaLayout { offset_u: 201, offset_v: 200, stride_x: 2, stride_y: 10 });
+		assert!(info.byte_size() == 300);
+
+		let info = ImageInfo::i420(10, 20, encoding);
+		assert!(info.chroma == ChromaLayout { offset_u: 200, offset_v: 250, stride_x: 1, stride_y: 5 });
+		assert!(info.byte_size() == 300);
+
+		// Odd sizes round the chroma planes up.
+		let info = ImageInfo::i420(5, 3, encoding);
+		assert!(info.chroma == ChromaLayout { offset_u: 15, offset_v: 21, stride_x: 1, stride_y: 3 });
+		assert!(info.byte_size() == 27);
+
+		let info = ImageInfo::yuyv(10, 20, encoding);
+		assert!(info.stride_x == 2);
+		assert!(info.stride_y == 20);
+		assert!(info.chroma == ChromaLayout { offset_u: 1, offset_v: 3, stride_x: 4, stride_y: 20 });
+		assert!(info.byte_size() == 400);
+
+		let info = ImageInfo::uyvy(10, 20, encoding);
+		assert!(info.chroma == ChromaLayout { offset_u: 0, offset_v: 2, stride_x: 4, stride_y: 20 });
+		assert!(info.byte_size() == 400);
+	}
+}
@@ -51,6 +51,22 @@
 //! To ensure that no data loss occurs, call [`exit()`] to terminate the process rather than [`std::process::exit()`].
 //! That will ensure that the background threads are joined before the process is terminated.
 //!
+//! # Keyboard shortcuts.
+//! Windows respond to a few keyboard shortcuts to change how the image is displayed:
+//!   * `C`: cycle through the colormaps for monochrome images.
+//!   * `E`: cycle through the automatic contrast enhancement modes.
+//!   * `F`: cycle through the sampling filters (nearest, bilinear and area).
+//!   * `R`, `G`, `B`, `A`: show only the red, green, blue or alpha channel as grayscale, press again to show all channels.
+//!   * `[`, `]`: rotate the image 90 degrees counterclockwise or clockwise.
+//!   * `X`, `Y`: mirror the image horizontally or vertically.
+//!   * Arrow keys or `H`, `J`, `K`, `L`: pan the image.
+//!   * `+`, `-`: zoom in or out around the center of the window.
+//!   * `0`: zoom and pan the image to fit the window.
+//!   * `1`: zoom to one window pixel per image pixel.
+//!
+//! The display shortcuts and the navigation keys can be disabled with [`WindowOptions::display_shortcuts`] and [`WindowOptions::keyboard_navigation`],
+//! for applications that use these keys themselves.
+//!
 //! # Example 1: Showing an image.
 //! ```no_run
 //! # use image;
@@ -121,6 +137,7 @@ mod background_thread;
 pub mod error;
 pub mod event;
 mod features;
+mod flow;
 mod image;
 mod image_info;
 mod oneshot;
@@ -128,6 +145,7 @@ mod rectangle;
 
 pub use self::backend::*;
 pub use self::features::*;
+pub use self::flow::flow_color_wheel;
 pub use self::image::*;
 pub use self::image_info::*;
 pub use self::rectangle::Rectangle;